@@ -1,5 +1,5 @@
 use dprint_core::configuration::{
-    get_nullable_value, get_unknown_property_diagnostics, get_value, ConfigKeyMap,
+    get_nullable_value, get_unknown_property_diagnostics, get_value, ConfigKeyMap, ConfigKeyValue,
     ConfigurationDiagnostic, GlobalConfiguration, NewLineKind, ResolveConfigurationResult,
 };
 use markup_fmt::config::*;
@@ -347,6 +347,38 @@ pub(crate) fn resolve_config(
                 "astroAttrShorthand",
                 &mut diagnostics,
             ),
+            html_go_template: get_value(&mut config, "htmlGoTemplate", false, &mut diagnostics),
+            front_matter: get_value(&mut config, "frontMatter", false, &mut diagnostics),
+            alpine: get_value(&mut config, "alpine", false, &mut diagnostics),
+            htmx: get_value(&mut config, "htmx", false, &mut diagnostics),
+            aurelia: get_value(&mut config, "aurelia", false, &mut diagnostics),
+            vento_custom_tags: match config.shift_remove("ventoCustomTags") {
+                Some(ConfigKeyValue::Object(tags)) => tags
+                    .into_iter()
+                    .filter_map(
+                        |(name, kind)| match kind.as_string().map(|kind| kind.as_str()) {
+                            Some("block") => Some((name, VentoTagKind::Block)),
+                            Some("raw") => Some((name, VentoTagKind::Raw)),
+                            Some("single") => Some((name, VentoTagKind::Single)),
+                            _ => {
+                                diagnostics.push(ConfigurationDiagnostic {
+                                    property_name: "ventoCustomTags".into(),
+                                    message: format!("invalid kind of Vento tag `{name}`"),
+                                });
+                                None
+                            }
+                        },
+                    )
+                    .collect(),
+                Some(ConfigKeyValue::Null) | None => Default::default(),
+                Some(_) => {
+                    diagnostics.push(ConfigurationDiagnostic {
+                        property_name: "ventoCustomTags".into(),
+                        message: "invalid value for config `ventoCustomTags`".into(),
+                    });
+                    Default::default()
+                }
+            },
             ignore_comment_directive: get_value(
                 &mut config,
                 "ignoreCommentDirective",
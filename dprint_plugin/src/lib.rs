@@ -46,7 +46,28 @@ impl SyncPluginHandler<FormatOptions> for MarkupFmtPluginHandler {
                     "jinja2",
                     "twig",
                     "njk",
+                    "tera",
+                    "askama",
                     "vto",
+                    "hbs",
+                    "handlebars",
+                    "mustache",
+                    "liquid",
+                    "gohtml",
+                    "tmpl",
+                    "erb",
+                    "eex",
+                    "heex",
+                    "blade.php",
+                    "phtml",
+                    "cshtml",
+                    "razor",
+                    "xml",
+                    "svg",
+                    "xhtml",
+                    "plist",
+                    "csproj",
+                    "mjml",
                     "component.html",
                 ]
                 .into_iter()
@@ -76,7 +97,7 @@ impl SyncPluginHandler<FormatOptions> for MarkupFmtPluginHandler {
         config: &FormatOptions,
         mut format_with_host: impl FnMut(&Path, Vec<u8>, &ConfigKeyMap) -> Result<Option<Vec<u8>>>,
     ) -> Result<Option<Vec<u8>>> {
-        // falling back to HTML allows to format files with unknown extensions, such as .svg
+        // falling back to HTML allows to format files with unknown extensions, such as .htm
         let language = detect_language(file_path).unwrap_or(markup_fmt::Language::Html);
 
         let format_result = format_text(
@@ -114,6 +135,7 @@ impl SyncPluginHandler<FormatOptions> for MarkupFmtPluginHandler {
                 );
                 Err(anyhow::anyhow!(msg))
             }
+            Err(err @ FormatError::InvalidPlaceholder(..)) => Err(anyhow::anyhow!(err.to_string())),
         }
     }
 }
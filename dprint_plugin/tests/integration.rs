@@ -69,7 +69,7 @@ fn integration_with_dprint_ts_snapshot() {
     }
 
     glob!(
-        "integration/**/*.{html,vue,svelte,astro,jinja,njk,vto}",
+        "integration/**/*.{html,vue,svelte,astro,jinja,njk,tera,askama,vto,hbs,mustache,liquid,gohtml,tmpl,erb,eex,heex,php,phtml,cshtml,razor,xml,svg,xhtml,plist,csproj,mjml}",
         |path| {
             let input = fs::read_to_string(path).unwrap();
             let output = format_with_dprint_ts(&input, path)
@@ -153,7 +153,7 @@ fn integration_with_biome_snapshot() {
     }
 
     glob!(
-        "integration/**/*.{html,vue,svelte,astro,jinja,njk,vto}",
+        "integration/**/*.{html,vue,svelte,astro,jinja,njk,tera,askama,vto,hbs,mustache,liquid,gohtml,tmpl,erb,eex,heex,php,phtml,cshtml,razor,xml,svg,xhtml,plist,csproj,mjml}",
         |path| {
             let file_name = path.file_name().and_then(|file_name| file_name.to_str());
             if let Some("return.astro") = file_name {
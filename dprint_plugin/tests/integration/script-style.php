@@ -0,0 +1,11 @@
+<script>
+var items = <?= json_encode($items) ?>;
+var user = <?php echo json_encode($user); ?>;
+<?php if ($debug): ?>
+console.log( items , "?>" );
+<?php endif; ?>
+</script>
+<style>
+.banner{background:<?= $color ?>;width:<?= $width ?>px}
+<?php /* theme ?> */ include 'theme.css.php'; ?>
+</style>
@@ -60,14 +60,49 @@ pub enum AstroExprChild<'s> {
 
 pub enum Attribute<'s> {
     Astro(AstroAttribute<'s>),
+    BladeDirectiveOrBlock(NodeKind<'s>),
+    ErbTagOrBlock(NodeKind<'s>),
+    GoTemplateTagOrBlock(NodeKind<'s>),
+    HandlebarsTagOrBlock(NodeKind<'s>),
     JinjaBlock(JinjaBlock<'s, Attribute<'s>>),
     JinjaTag(JinjaTag<'s>),
     Native(NativeAttribute<'s>),
+    PhpTagOrBlock(NodeKind<'s>),
     Svelte(SvelteAttribute<'s>),
+    SvelteAttachment(SvelteAttachment<'s>),
     VentoTagOrBlock(NodeKind<'s>),
     VueDirective(VueDirective<'s>),
 }
 
+pub struct BladeBlock<'s> {
+    pub body: Vec<BladeDirectiveOrChildren<'s>>,
+}
+
+pub struct BladeComment<'s> {
+    pub raw: &'s str,
+}
+
+pub struct BladeDirective<'s> {
+    pub name: &'s str,
+    pub args: Option<(&'s str, usize)>,
+}
+
+pub enum BladeDirectiveOrChildren<'s> {
+    Directive(BladeDirective<'s>),
+    Children(Vec<Node<'s>>),
+}
+
+pub struct BladeEcho<'s> {
+    pub expr: &'s str,
+    /// `{{ }}` is escaped, while `{!! !!}` isn't.
+    pub escaped: bool,
+}
+
+/// XML CDATA section like `<![CDATA[ ]]>`.
+pub struct Cdata<'s> {
+    pub raw: &'s str,
+}
+
 pub struct Comment<'s> {
     pub raw: &'s str,
 }
@@ -86,9 +121,88 @@ pub struct Element<'s> {
     pub void_element: bool,
 }
 
+pub struct ErbBlock<'s> {
+    pub body: Vec<ErbTagOrChildren<'s>>,
+}
+
+pub struct ErbComment<'s> {
+    pub raw: &'s str,
+    /// HEEx comment like `<%!-- --%>`.
+    pub long: bool,
+}
+
+pub struct ErbTag<'s> {
+    /// Characters after `<%` such as `=` or `-`.
+    pub prefix: &'s str,
+    pub code: &'s str,
+    pub start: usize,
+    pub trim_next: bool,
+}
+
+pub enum ErbTagOrChildren<'s> {
+    Tag(ErbTag<'s>),
+    Children(Vec<Node<'s>>),
+}
+
 pub struct FrontMatter<'s> {
     pub raw: &'s str,
     pub start: usize,
+    /// TOML front matter delimited by `+++`, instead of YAML by `---`.
+    pub toml: bool,
+}
+
+pub struct GoTemplateBlock<'s> {
+    pub body: Vec<GoTemplateTagOrChildren<'s>>,
+}
+
+pub struct GoTemplateComment<'s> {
+    pub raw: &'s str,
+    pub trim_prev: bool,
+    pub trim_next: bool,
+}
+
+pub struct GoTemplateInterpolation<'s> {
+    pub expr: &'s str,
+    pub trim_prev: bool,
+    pub trim_next: bool,
+}
+
+pub struct GoTemplateTag<'s> {
+    pub tag: &'s str,
+    pub trim_prev: bool,
+    pub trim_next: bool,
+}
+
+pub enum GoTemplateTagOrChildren<'s> {
+    Tag(GoTemplateTag<'s>),
+    Children(Vec<Node<'s>>),
+}
+
+pub struct HandlebarsBlock<'s> {
+    pub body: Vec<HandlebarsTagOrChildren<'s>>,
+}
+
+pub struct HandlebarsComment<'s> {
+    pub raw: &'s str,
+    pub long: bool,
+}
+
+pub struct HandlebarsInterpolation<'s> {
+    pub expr: &'s str,
+    pub triple: bool,
+    pub trim_prev: bool,
+    pub trim_next: bool,
+}
+
+pub struct HandlebarsTag<'s> {
+    pub tag: &'s str,
+    pub trim_prev: bool,
+    pub trim_next: bool,
+}
+
+pub enum HandlebarsTagOrChildren<'s> {
+    Tag(HandlebarsTag<'s>),
+    Children(Vec<Node<'s>>),
 }
 
 pub struct JinjaBlock<'s, T> {
@@ -130,14 +244,38 @@ pub enum NodeKind<'s> {
     AngularLet(AngularLet<'s>),
     AngularSwitch(AngularSwitch<'s>),
     AstroExpr(AstroExpr<'s>),
+    BladeBlock(BladeBlock<'s>),
+    BladeComment(BladeComment<'s>),
+    BladeDirective(BladeDirective<'s>),
+    BladeEcho(BladeEcho<'s>),
+    Cdata(Cdata<'s>),
     Comment(Comment<'s>),
     Doctype(Doctype<'s>),
     Element(Element<'s>),
+    ErbBlock(ErbBlock<'s>),
+    ErbComment(ErbComment<'s>),
+    ErbTag(ErbTag<'s>),
     FrontMatter(FrontMatter<'s>),
+    GoTemplateBlock(GoTemplateBlock<'s>),
+    GoTemplateComment(GoTemplateComment<'s>),
+    GoTemplateInterpolation(GoTemplateInterpolation<'s>),
+    HandlebarsBlock(HandlebarsBlock<'s>),
+    HandlebarsComment(HandlebarsComment<'s>),
+    HandlebarsInterpolation(HandlebarsInterpolation<'s>),
+    HandlebarsTag(HandlebarsTag<'s>),
     JinjaBlock(JinjaBlock<'s, Node<'s>>),
     JinjaComment(JinjaComment<'s>),
     JinjaInterpolation(JinjaInterpolation<'s>),
     JinjaTag(JinjaTag<'s>),
+    PhpBlock(PhpBlock<'s>),
+    PhpTag(PhpTag<'s>),
+    ProcessingInstruction(ProcessingInstruction<'s>),
+    RazorCodeBlock(RazorCodeBlock<'s>),
+    RazorComment(RazorComment<'s>),
+    RazorControlFlow(RazorControlFlow<'s>),
+    RazorDirective(RazorDirective<'s>),
+    RazorExpr(RazorExpr<'s>),
+    RazorStatement(RazorStatement<'s>),
     SvelteAtTag(SvelteAtTag<'s>),
     SvelteAwaitBlock(Box<SvelteAwaitBlock<'s>>),
     SvelteEachBlock(SvelteEachBlock<'s>),
@@ -154,6 +292,73 @@ pub enum NodeKind<'s> {
     VueInterpolation(VueInterpolation<'s>),
 }
 
+pub struct PhpBlock<'s> {
+    pub body: Vec<PhpTagOrChildren<'s>>,
+}
+
+pub struct PhpTag<'s> {
+    pub code: &'s str,
+    pub start: usize,
+    /// Short echo tag like `<?= $name ?>`.
+    pub echo: bool,
+    /// Whether tag is closed by `?>`, which can be omitted at the end of file.
+    pub closed: bool,
+}
+
+pub enum PhpTagOrChildren<'s> {
+    Tag(PhpTag<'s>),
+    Children(Vec<Node<'s>>),
+}
+
+/// XML processing instruction or declaration like `<?xml version="1.0"?>`.
+pub struct ProcessingInstruction<'s> {
+    pub raw: &'s str,
+}
+
+/// Razor block like `@{ }`, whose children are C# statements or markup.
+pub struct RazorCodeBlock<'s> {
+    pub children: Vec<Node<'s>>,
+}
+
+pub struct RazorComment<'s> {
+    pub raw: &'s str,
+}
+
+/// Razor control structure like `@if (a) { } else { }` or `@section Scripts { }`.
+pub struct RazorControlFlow<'s> {
+    /// Control structures inside code block don't start with `@`.
+    pub at_sign: bool,
+    pub branches: Vec<RazorControlFlowBranch<'s>>,
+    /// The `while (cond);` part of `do { } while (cond);`.
+    pub trailer: Option<&'s str>,
+}
+
+pub struct RazorControlFlowBranch<'s> {
+    /// Code before `{`, like `if (a)` or `else`. It's empty for bare block.
+    pub header: &'s str,
+    pub children: Vec<Node<'s>>,
+}
+
+/// Razor directive like `@model Foo` or `@code { }`.
+pub struct RazorDirective<'s> {
+    pub name: &'s str,
+    pub value: &'s str,
+    /// C# code of `@code { }` or `@functions { }`.
+    pub body: Option<(&'s str, usize)>,
+}
+
+/// Razor implicit expression `@expr` or explicit expression `@(expr)`.
+pub struct RazorExpr<'s> {
+    pub expr: &'s str,
+    pub explicit: bool,
+}
+
+/// C# statement or `@:` text line inside Razor code block.
+pub struct RazorStatement<'s> {
+    pub code: &'s str,
+    pub start: usize,
+}
+
 pub struct Root<'s> {
     pub children: Vec<Node<'s>>,
 }
@@ -163,6 +368,10 @@ pub struct SvelteAtTag<'s> {
     pub expr: (&'s str, usize),
 }
 
+pub struct SvelteAttachment<'s> {
+    pub expr: (&'s str, usize),
+}
+
 pub struct SvelteAttribute<'s> {
     pub name: Option<&'s str>,
     pub expr: (&'s str, usize),
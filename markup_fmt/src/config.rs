@@ -5,7 +5,7 @@
 
 #[cfg(feature = "config_serde")]
 use serde::{Deserialize, Serialize};
-use std::num::NonZeroUsize;
+use std::{collections::HashMap, num::NonZeroUsize};
 
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "config_serde", derive(Serialize, Deserialize))]
@@ -212,6 +212,21 @@ pub struct LanguageOptions {
     #[cfg_attr(feature = "config_serde", serde(alias = "astroAttrShorthand"))]
     pub astro_attr_shorthand: Option<bool>,
 
+    #[cfg_attr(feature = "config_serde", serde(alias = "htmlGoTemplate"))]
+    pub html_go_template: bool,
+
+    #[cfg_attr(feature = "config_serde", serde(alias = "frontMatter"))]
+    pub front_matter: bool,
+
+    pub alpine: bool,
+
+    pub htmx: bool,
+
+    pub aurelia: bool,
+
+    #[cfg_attr(feature = "config_serde", serde(alias = "ventoCustomTags"))]
+    pub vento_custom_tags: HashMap<String, VentoTagKind>,
+
     #[cfg_attr(feature = "config_serde", serde(alias = "ignoreCommentDirective"))]
     pub ignore_comment_directive: String,
 
@@ -258,6 +273,12 @@ impl Default for LanguageOptions {
             svelte_attr_shorthand: None,
             svelte_directive_shorthand: None,
             astro_attr_shorthand: None,
+            html_go_template: false,
+            front_matter: false,
+            alpine: false,
+            htmx: false,
+            aurelia: false,
+            vento_custom_tags: HashMap::new(),
             ignore_comment_directive: "markup-fmt-ignore".into(),
             ignore_file_comment_directive: "markup-fmt-ignore-file".into(),
         }
@@ -340,3 +361,16 @@ pub enum VSlotStyle {
     #[cfg_attr(feature = "config_serde", serde(alias = "vSlot", alias = "vslot"))]
     VSlot,
 }
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "config_serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "config_serde", serde(rename_all = "kebab-case"))]
+/// Kind of custom Vento tag provided by plugins.
+pub enum VentoTagKind {
+    /// Tag with body which is closed by `{{ /tag }}`.
+    Block,
+    /// Like [`VentoTagKind::Block`], but its body is kept as is.
+    Raw,
+    /// Tag without body.
+    Single,
+}
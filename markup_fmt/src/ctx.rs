@@ -30,7 +30,21 @@ where
 {
     pub(crate) fn script_indent(&self) -> bool {
         match self.language {
-            Language::Html | Language::Jinja | Language::Vento | Language::Angular => self
+            Language::Html
+            | Language::Jinja
+            | Language::Vento
+            | Language::Angular
+            | Language::Handlebars
+            | Language::Liquid
+            | Language::GoTemplate
+            | Language::Erb
+            | Language::Eex
+            | Language::Heex
+            | Language::Blade
+            | Language::Php
+            | Language::Razor
+            | Language::Xml
+            | Language::Mjml => self
                 .options
                 .html_script_indent
                 .unwrap_or(self.options.script_indent),
@@ -51,7 +65,21 @@ where
 
     pub(crate) fn style_indent(&self) -> bool {
         match self.language {
-            Language::Html | Language::Jinja | Language::Vento | Language::Angular => self
+            Language::Html
+            | Language::Jinja
+            | Language::Vento
+            | Language::Angular
+            | Language::Handlebars
+            | Language::Liquid
+            | Language::GoTemplate
+            | Language::Erb
+            | Language::Eex
+            | Language::Heex
+            | Language::Blade
+            | Language::Php
+            | Language::Razor
+            | Language::Xml
+            | Language::Mjml => self
                 .options
                 .html_style_indent
                 .unwrap_or(self.options.style_indent),
@@ -154,6 +182,39 @@ where
         }
     }
 
+    pub(crate) fn format_fn_body(&mut self, code: &str, attr: bool, start: usize) -> String {
+        if code.trim().is_empty() {
+            String::new()
+        } else {
+            let wrapped = self
+                .source
+                .get(0..start.saturating_sub(14))
+                .unwrap_or_default()
+                .replace(|c: char| !c.is_ascii_whitespace(), " ")
+                + "function _() {"
+                + code.trim()
+                + "\n}";
+            let formatted = self.format_with_external_formatter(
+                wrapped,
+                Hints {
+                    print_width: self
+                        .print_width
+                        .saturating_sub(self.indent_level)
+                        .saturating_sub(2), // this is technically wrong, just workaround
+                    attr,
+                    ext: "ts",
+                },
+            );
+            let formatted = formatted.trim();
+            formatted
+                .strip_prefix("function _() {")
+                .and_then(|s| s.strip_suffix('}'))
+                .unwrap_or(formatted)
+                .trim()
+                .to_owned()
+        }
+    }
+
     pub(crate) fn format_binding(&mut self, code: &str, start: usize) -> String {
         if code.trim().is_empty() {
             String::new()
@@ -246,6 +307,81 @@ where
         }
     }
 
+    /// Format function signature like `name(a, b = 1)`, without the `function` keyword.
+    pub(crate) fn format_function_signature(&mut self, code: &str) -> String {
+        if code.trim().is_empty() {
+            String::new()
+        } else {
+            let wrapped = format!("function {} {{}}", code.trim());
+            let formatted = self.format_with_external_formatter(
+                wrapped,
+                Hints {
+                    print_width: self
+                        .print_width
+                        .saturating_sub(self.indent_level)
+                        .saturating_sub(9), // this is technically wrong, just workaround
+                    attr: false,
+                    ext: "js",
+                },
+            );
+            formatted
+                .trim()
+                .strip_prefix("function")
+                .and_then(|s| s.strip_suffix('}'))
+                .and_then(|s| s.trim_end().strip_suffix('{'))
+                .map(|s| s.trim())
+                .unwrap_or(code.trim())
+                .to_owned()
+        }
+    }
+
+    /// Format PHP code without opening tag `<?php`.
+    pub(crate) fn format_php(&mut self, code: &str, start: usize) -> String {
+        if code.trim().is_empty() {
+            String::new()
+        } else {
+            let wrapped = self
+                .source
+                .get(0..start.saturating_sub(6))
+                .unwrap_or_default()
+                .replace(|c: char| !c.is_ascii_whitespace(), " ")
+                + "<?php "
+                + code.trim();
+            let formatted = self.format_with_external_formatter(
+                wrapped,
+                Hints {
+                    print_width: self
+                        .print_width
+                        .saturating_sub(self.indent_level)
+                        .saturating_sub(self.indent_width),
+                    attr: false,
+                    ext: "php",
+                },
+            );
+            let formatted = formatted.trim();
+            formatted
+                .strip_prefix("<?php")
+                .unwrap_or(formatted)
+                .trim()
+                .to_owned()
+        }
+    }
+
+    /// Format PHP expression of echo tag `<?= expr ?>`.
+    /// It's wrapped as `echo` statement, since bare expression without `;` isn't valid PHP.
+    pub(crate) fn format_php_expr(&mut self, code: &str, start: usize) -> String {
+        let formatted = self.format_php(
+            &format!("echo {};", code.trim()),
+            start.saturating_sub("echo ".len()),
+        );
+        formatted
+            .strip_prefix("echo")
+            .and_then(|s| s.strip_suffix(';'))
+            .map(|s| s.trim())
+            .unwrap_or(code.trim())
+            .to_owned()
+    }
+
     pub(crate) fn format_script<'a>(
         &mut self,
         code: &'a str,
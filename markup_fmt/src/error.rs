@@ -1,4 +1,4 @@
-use std::{borrow::Cow, error::Error, fmt};
+use std::{borrow::Cow, error::Error, fmt, ops::Range};
 
 #[derive(Clone, Debug)]
 /// Syntax error when parsing tags, not `<script>` or `<style>` tag.
@@ -19,19 +19,38 @@ pub enum SyntaxErrorKind {
     ExpectAstroExpr,
     ExpectAttrName,
     ExpectAttrValue,
+    ExpectBladeBlockEnd,
+    ExpectBladeComment,
+    ExpectBladeDirective,
+    ExpectBladeEcho,
     ExpectChar(char),
+    ExpectCdata,
     ExpectCloseTag,
     ExpectComment,
     ExpectDoctype,
     ExpectElement,
+    ExpectErbBlockEnd,
+    ExpectErbComment,
+    ExpectErbTag,
     ExpectFrontMatter,
+    ExpectGoTemplateBlockEnd,
+    ExpectHandlebarsBlockEnd,
+    ExpectHandlebarsComment,
     ExpectIdentifier,
     ExpectJinjaBlockEnd,
     ExpectJinjaTag,
     ExpectKeyword(&'static str),
     ExpectMustacheInterpolation,
+    ExpectPhpBlockEnd,
+    ExpectPhpTag,
+    ExpectProcessingInstruction,
+    ExpectRazorBlock,
+    ExpectRazorBlockEnd,
+    ExpectRazorComment,
+    ExpectRazorExpr,
     ExpectSelfCloseTag,
     ExpectSvelteAtTag,
+    ExpectSvelteAttachment,
     ExpectSvelteAttr,
     ExpectSvelteAwaitBlock,
     ExpectSvelteBlockEnd,
@@ -41,6 +60,7 @@ pub enum SyntaxErrorKind {
     ExpectSvelteIfBlock,
     ExpectSvelteInterpolation,
     ExpectSvelteKeyBlock,
+    ExpectSvelteRenderCall,
     ExpectSvelteSnippetBlock,
     ExpectSvelteThenBlock,
     ExpectTagName,
@@ -60,12 +80,23 @@ impl fmt::Display for SyntaxErrorKind {
             SyntaxErrorKind::ExpectAstroExpr => "expected Astro expression".into(),
             SyntaxErrorKind::ExpectAttrName => "expected attribute name".into(),
             SyntaxErrorKind::ExpectAttrValue => "expected attribute value".into(),
+            SyntaxErrorKind::ExpectBladeBlockEnd => "expected Blade `@end` directive".into(),
+            SyntaxErrorKind::ExpectBladeComment => "expected Blade comment".into(),
+            SyntaxErrorKind::ExpectBladeDirective => "expected Blade directive".into(),
+            SyntaxErrorKind::ExpectBladeEcho => "expected Blade echo".into(),
             SyntaxErrorKind::ExpectChar(c) => format!("expected char '{c}'").into(),
+            SyntaxErrorKind::ExpectCdata => "expected CDATA section".into(),
             SyntaxErrorKind::ExpectCloseTag => "expected close tag".into(),
             SyntaxErrorKind::ExpectComment => "expected comment".into(),
             SyntaxErrorKind::ExpectDoctype => "expected HTML doctype".into(),
             SyntaxErrorKind::ExpectElement => "expected element".into(),
+            SyntaxErrorKind::ExpectErbBlockEnd => "expected `<% end %>`".into(),
+            SyntaxErrorKind::ExpectErbComment => "expected ERB comment".into(),
+            SyntaxErrorKind::ExpectErbTag => "expected ERB tag".into(),
             SyntaxErrorKind::ExpectFrontMatter => "expected front matter".into(),
+            SyntaxErrorKind::ExpectGoTemplateBlockEnd => "expected Go template `{{ end }}`".into(),
+            SyntaxErrorKind::ExpectHandlebarsBlockEnd => "expected Handlebars block end".into(),
+            SyntaxErrorKind::ExpectHandlebarsComment => "expected Handlebars comment".into(),
             SyntaxErrorKind::ExpectIdentifier => "expected identifier".into(),
             SyntaxErrorKind::ExpectJinjaBlockEnd => "expected Jinja block end".into(),
             SyntaxErrorKind::ExpectJinjaTag => "expected Jinja tag".into(),
@@ -75,8 +106,18 @@ impl fmt::Display for SyntaxErrorKind {
             SyntaxErrorKind::ExpectMustacheInterpolation => {
                 "expected mustache-like interpolation".into()
             }
+            SyntaxErrorKind::ExpectPhpBlockEnd => "expected PHP `end` statement".into(),
+            SyntaxErrorKind::ExpectPhpTag => "expected PHP tag".into(),
+            SyntaxErrorKind::ExpectProcessingInstruction => {
+                "expected processing instruction".into()
+            }
+            SyntaxErrorKind::ExpectRazorBlock => "expected Razor block".into(),
+            SyntaxErrorKind::ExpectRazorBlockEnd => "expected `}` of Razor block".into(),
+            SyntaxErrorKind::ExpectRazorComment => "expected Razor comment".into(),
+            SyntaxErrorKind::ExpectRazorExpr => "expected Razor expression".into(),
             SyntaxErrorKind::ExpectSelfCloseTag => "expected self close tag".into(),
             SyntaxErrorKind::ExpectSvelteAtTag => "expected Svelte `{@` tag".into(),
+            SyntaxErrorKind::ExpectSvelteAttachment => "expected Svelte attachment".into(),
             SyntaxErrorKind::ExpectSvelteAttr => "expected Svelte attribute".into(),
             SyntaxErrorKind::ExpectSvelteAwaitBlock => "expected Svelte await block".into(),
             SyntaxErrorKind::ExpectSvelteBlockEnd => "expected end of Svelte block".into(),
@@ -86,6 +127,9 @@ impl fmt::Display for SyntaxErrorKind {
             SyntaxErrorKind::ExpectSvelteIfBlock => "expected Svelte if block".into(),
             SyntaxErrorKind::ExpectSvelteInterpolation => "expected Svelte interpolation".into(),
             SyntaxErrorKind::ExpectSvelteKeyBlock => "expected Svelte key block".into(),
+            SyntaxErrorKind::ExpectSvelteRenderCall => {
+                "expected call expression in Svelte `{@render}` tag".into()
+            }
             SyntaxErrorKind::ExpectSvelteSnippetBlock => "expected Svelte snippet block".into(),
             SyntaxErrorKind::ExpectSvelteThenBlock => "expected Svelte then block".into(),
             SyntaxErrorKind::ExpectTagName => "expected tag name".into(),
@@ -118,6 +162,9 @@ pub enum FormatError<E> {
     /// Error from external formatter, for example,
     /// there're errors when formatting the `<script>` or `<style>` tag.
     External(Vec<E>),
+    /// Placeholder range passed to [`format_fragment`](crate::format_fragment)
+    /// overlaps with another one or isn't valid byte range of source code.
+    InvalidPlaceholder(Range<usize>),
 }
 
 impl<E> fmt::Display for FormatError<E>
@@ -134,6 +181,9 @@ where
                 }
                 Ok(())
             }
+            FormatError::InvalidPlaceholder(range) => {
+                write!(f, "invalid placeholder range {range:?}")
+            }
         }
     }
 }
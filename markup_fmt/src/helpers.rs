@@ -1,6 +1,37 @@
-use crate::Language;
+use crate::{
+    ast::{Attribute, NativeAttribute, VueDirective},
+    config::VentoTagKind,
+    Language,
+};
 use aho_corasick::AhoCorasick;
-use std::sync::LazyLock;
+use std::{collections::HashMap, sync::LazyLock};
+
+/// Check if children of element won't be compiled by framework,
+/// such as `v-pre` in Vue and `ngNonBindable` in Angular.
+pub(crate) fn has_non_compiled_attr(attrs: &[Attribute], language: &Language) -> bool {
+    match language {
+        Language::Vue => attrs.iter().any(|attr| {
+            matches!(
+                attr,
+                Attribute::VueDirective(VueDirective {
+                    name: "pre",
+                    arg_and_modifiers: None,
+                    ..
+                })
+            )
+        }),
+        Language::Angular => attrs.iter().any(|attr| {
+            matches!(
+                attr,
+                Attribute::Native(NativeAttribute {
+                    name: "ngNonBindable",
+                    ..
+                })
+            )
+        }),
+        _ => false,
+    }
+}
 
 pub(crate) fn is_component(name: &str) -> bool {
     name.contains('-') || name.contains(|c: char| c.is_ascii_uppercase())
@@ -83,8 +114,107 @@ static NON_WS_SENSITIVE_TAGS: [&str; 74] = [
     "picture",
 ];
 
+/// Content model of MJML tags.
+#[derive(Clone, Copy)]
+pub(crate) enum MjmlContent {
+    /// Contains other MJML tags, like `mj-section`.
+    Mjml,
+    /// Contains HTML, like `mj-text`.
+    Html,
+    /// Contains raw content which must be kept verbatim, like `mj-raw`.
+    Raw,
+    /// Contains CSS, like `mj-style`.
+    Css,
+}
+
+static MJML_TAGS: [(&str, MjmlContent); 37] = [
+    ("mjml", MjmlContent::Mjml),
+    ("mj-head", MjmlContent::Mjml),
+    ("mj-body", MjmlContent::Mjml),
+    ("mj-attributes", MjmlContent::Mjml),
+    ("mj-all", MjmlContent::Mjml),
+    ("mj-class", MjmlContent::Mjml),
+    ("mj-breakpoint", MjmlContent::Mjml),
+    ("mj-font", MjmlContent::Mjml),
+    ("mj-html-attributes", MjmlContent::Mjml),
+    ("mj-html-attribute", MjmlContent::Html),
+    ("mj-selector", MjmlContent::Mjml),
+    ("mj-include", MjmlContent::Mjml),
+    ("mj-title", MjmlContent::Html),
+    ("mj-preview", MjmlContent::Html),
+    ("mj-style", MjmlContent::Css),
+    ("mj-raw", MjmlContent::Raw),
+    ("mj-wrapper", MjmlContent::Mjml),
+    ("mj-section", MjmlContent::Mjml),
+    ("mj-group", MjmlContent::Mjml),
+    ("mj-column", MjmlContent::Mjml),
+    ("mj-hero", MjmlContent::Mjml),
+    ("mj-text", MjmlContent::Html),
+    ("mj-button", MjmlContent::Html),
+    ("mj-image", MjmlContent::Mjml),
+    ("mj-divider", MjmlContent::Mjml),
+    ("mj-spacer", MjmlContent::Mjml),
+    ("mj-table", MjmlContent::Html),
+    ("mj-accordion", MjmlContent::Mjml),
+    ("mj-accordion-element", MjmlContent::Mjml),
+    ("mj-accordion-title", MjmlContent::Html),
+    ("mj-accordion-text", MjmlContent::Html),
+    ("mj-carousel", MjmlContent::Mjml),
+    ("mj-carousel-image", MjmlContent::Mjml),
+    ("mj-navbar", MjmlContent::Mjml),
+    ("mj-navbar-link", MjmlContent::Html),
+    ("mj-social", MjmlContent::Mjml),
+    ("mj-social-element", MjmlContent::Html),
+];
+
+/// Get content model of MJML tag, or `None` if it isn't a known MJML tag.
+pub(crate) fn get_mjml_content(name: &str) -> Option<MjmlContent> {
+    MJML_TAGS
+        .iter()
+        .find(|(tag, _)| tag.eq_ignore_ascii_case(name))
+        .map(|(_, content)| *content)
+}
+
+/// Check if it's a raw text or escapable raw text (RCDATA) element defined by HTML spec,
+/// except `script`, `style` and `textarea` which are handled separately.
+/// `noscript` is treated as raw text as if scripting is enabled.
+/// This applies to HTML-based languages as well, while XML and MJML elements aren't HTML elements.
+pub(crate) fn is_raw_text_element(name: &str, language: Language) -> bool {
+    !matches!(language, Language::Xml | Language::Mjml)
+        && [
+            "title",
+            "xmp",
+            "plaintext",
+            "noembed",
+            "noframes",
+            "iframe",
+            "noscript",
+        ]
+        .iter()
+        .any(|tag| tag.eq_ignore_ascii_case(name))
+}
+
 pub(crate) fn is_whitespace_sensitive_tag(name: &str, language: Language) -> bool {
-    if matches!(language, Language::Html | Language::Jinja | Language::Vento) {
+    if matches!(language, Language::Xml) {
+        // XML elements don't have CSS `display` like HTML inline elements.
+        false
+    } else if let (Language::Mjml, Some(content)) = (&language, get_mjml_content(name)) {
+        // MJML tags are rendered as blocks, including `mj-text` and `mj-button`,
+        // while `mj-raw` is copied to the output as is.
+        matches!(content, MjmlContent::Raw)
+    } else if matches!(
+        language,
+        Language::Html
+            | Language::Jinja
+            | Language::Vento
+            | Language::Liquid
+            | Language::GoTemplate
+            | Language::Erb
+            | Language::Eex
+            | Language::Blade
+            | Language::Php
+            | Language::Mjml
+    ) {
         // There's also a tag called "a" in SVG, so we need to check it specially.
         name.eq_ignore_ascii_case("a")
             || !NON_WS_SENSITIVE_TAGS
@@ -106,7 +236,21 @@ static VOID_ELEMENTS: [&str; 14] = [
 ];
 
 pub(crate) fn is_void_element(name: &str, language: Language) -> bool {
-    if matches!(language, Language::Html | Language::Jinja | Language::Vento) {
+    if matches!(language, Language::Xml) {
+        false
+    } else if matches!(
+        language,
+        Language::Html
+            | Language::Jinja
+            | Language::Vento
+            | Language::Liquid
+            | Language::GoTemplate
+            | Language::Erb
+            | Language::Eex
+            | Language::Blade
+            | Language::Php
+            | Language::Mjml
+    ) {
         VOID_ELEMENTS
             .iter()
             .any(|tag| tag.eq_ignore_ascii_case(name))
@@ -116,7 +260,21 @@ pub(crate) fn is_void_element(name: &str, language: Language) -> bool {
 }
 
 pub(crate) fn is_html_tag(name: &str, language: Language) -> bool {
-    if matches!(language, Language::Html | Language::Jinja | Language::Vento) {
+    if matches!(language, Language::Xml) {
+        false
+    } else if matches!(
+        language,
+        Language::Html
+            | Language::Jinja
+            | Language::Vento
+            | Language::Liquid
+            | Language::GoTemplate
+            | Language::Erb
+            | Language::Eex
+            | Language::Blade
+            | Language::Php
+            | Language::Mjml
+    ) {
         css_dataset::tags::STANDARD_HTML_TAGS
             .iter()
             .any(|tag| tag.eq_ignore_ascii_case(name))
@@ -134,7 +292,19 @@ pub(crate) fn is_html_tag(name: &str, language: Language) -> bool {
 }
 
 pub(crate) fn is_svg_tag(name: &str, language: Language) -> bool {
-    if matches!(language, Language::Html | Language::Jinja | Language::Vento) {
+    if matches!(
+        language,
+        Language::Html
+            | Language::Jinja
+            | Language::Vento
+            | Language::Liquid
+            | Language::GoTemplate
+            | Language::Erb
+            | Language::Eex
+            | Language::Blade
+            | Language::Php
+            | Language::Mjml
+    ) {
         css_dataset::tags::SVG_TAGS
             .iter()
             .any(|tag| tag.eq_ignore_ascii_case(name))
@@ -144,7 +314,19 @@ pub(crate) fn is_svg_tag(name: &str, language: Language) -> bool {
 }
 
 pub(crate) fn is_mathml_tag(name: &str, language: Language) -> bool {
-    if matches!(language, Language::Html | Language::Jinja | Language::Vento) {
+    if matches!(
+        language,
+        Language::Html
+            | Language::Jinja
+            | Language::Vento
+            | Language::Liquid
+            | Language::GoTemplate
+            | Language::Erb
+            | Language::Eex
+            | Language::Blade
+            | Language::Php
+            | Language::Mjml
+    ) {
         css_dataset::tags::MATH_ML_TAGS
             .iter()
             .any(|tag| tag.eq_ignore_ascii_case(name))
@@ -155,6 +337,20 @@ pub(crate) fn is_mathml_tag(name: &str, language: Language) -> bool {
     }
 }
 
+/// Split whitespace control markers like `-`, `+` and `~` (Askama)
+/// from both sides of Jinja tag or interpolation.
+pub(crate) fn split_jinja_whitespace_control(content: &str) -> (&str, &str, &str) {
+    let (prefix, content) = content
+        .strip_prefix(['-', '+', '~'])
+        .map(|rest| (&content[..1], rest))
+        .unwrap_or(("", content));
+    let (content, suffix) = content
+        .strip_suffix(['-', '+', '~'])
+        .map(|rest| (rest, &content[rest.len()..]))
+        .unwrap_or((content, ""));
+    (prefix, content, suffix)
+}
+
 pub(crate) fn parse_vento_tag(tag: &str) -> (&str, &str) {
     let trimmed = tag.trim();
     trimmed
@@ -162,5 +358,226 @@ pub(crate) fn parse_vento_tag(tag: &str) -> (&str, &str) {
         .unwrap_or((trimmed, ""))
 }
 
+pub(crate) fn is_vento_interpolation(tag_name: &str) -> bool {
+    !matches!(
+        tag_name,
+        "if" | "else"
+            | "for"
+            | "set"
+            | "include"
+            | "layout"
+            | "async"
+            | "function"
+            | "import"
+            | "export"
+            | "echo"
+    )
+}
+
+/// Check if Vento tag starts a block whose body is kept as is,
+/// like `{{ echo }}` or custom tags configured as raw.
+pub(crate) fn is_vento_raw_block(tag: &str, custom_tags: &HashMap<String, VentoTagKind>) -> bool {
+    let (tag_name, rest) = parse_vento_tag(tag);
+    if let Some(kind) = custom_tags.get(tag_name) {
+        matches!(kind, VentoTagKind::Raw)
+    } else {
+        // `{{ echo "text" }}` outputs its argument directly without body
+        tag_name == "echo" && (rest.is_empty() || rest.starts_with("|>"))
+    }
+}
+
+/// Role of an ERB, EEx or PHP tag in block structure.
+pub(crate) enum TemplateTagKind {
+    /// Tag that opens a block, like `<% if x %>` or `<?php foreach ($a as $b): ?>`.
+    Open,
+    /// Tag that separates branches of a block, like `<% else %>` or `<?php else: ?>`.
+    Middle,
+    /// Tag that closes a block, like `<% end %>` or `<?php endif; ?>`.
+    Close,
+    Other,
+}
+
+/// Detect whether ERB (Ruby) or EEx (Elixir) tag code opens, continues or closes a block.
+pub(crate) fn get_erb_tag_kind(code: &str, language: &Language) -> TemplateTagKind {
+    fn is_ident_char(c: char) -> bool {
+        c.is_alphanumeric() || c == '_'
+    }
+    fn ends_with_word(code: &str, word: &str) -> bool {
+        code.strip_suffix(word)
+            .is_some_and(|rest| !rest.ends_with(is_ident_char) && !rest.ends_with('.'))
+    }
+
+    let code = code.trim();
+    let first_word = code.split(|c| !is_ident_char(c)).next().unwrap_or_default();
+    if first_word == "end" {
+        return TemplateTagKind::Close;
+    }
+    if matches!(language, Language::Erb) {
+        if matches!(
+            first_word,
+            "else" | "elsif" | "when" | "in" | "rescue" | "ensure"
+        ) {
+            TemplateTagKind::Middle
+        } else if ends_with_word(code, "end") {
+            // one-liner like `if a then b else c end`
+            TemplateTagKind::Other
+        } else if matches!(
+            first_word,
+            "if" | "unless" | "case" | "while" | "until" | "for" | "begin"
+        ) || ends_with_word(code, "do")
+            || code
+                .strip_suffix('|')
+                .and_then(|rest| rest.rsplit_once('|'))
+                .is_some_and(|(rest, _)| ends_with_word(rest.trim_end(), "do"))
+        {
+            TemplateTagKind::Open
+        } else {
+            TemplateTagKind::Other
+        }
+    } else if matches!(first_word, "else" | "catch" | "rescue" | "after") {
+        TemplateTagKind::Middle
+    } else if ends_with_word(code, "do") {
+        TemplateTagKind::Open
+    } else if code.ends_with("->") {
+        if code.split(|c| !is_ident_char(c)).any(|word| word == "fn") {
+            TemplateTagKind::Open
+        } else {
+            // clause of `case`, `cond` or `with`
+            TemplateTagKind::Middle
+        }
+    } else {
+        TemplateTagKind::Other
+    }
+}
+
+/// Check if text starts with PHP open tag `<?php` or short echo tag `<?=`.
+pub(crate) fn is_php_tag_start(text: &str) -> bool {
+    text.starts_with("<?=")
+        || text
+            .get(..5)
+            .is_some_and(|tag| tag.eq_ignore_ascii_case("<?php"))
+            && text[5..]
+                .chars()
+                .next()
+                .is_none_or(|c| c.is_ascii_whitespace())
+}
+
+/// Detect whether PHP code opens, continues or closes a block,
+/// either in alternative syntax like `if ($a):` or with braces like `if ($a) {`.
+pub(crate) fn get_php_tag_kind(code: &str) -> TemplateTagKind {
+    let code = code.trim().trim_end_matches(';').trim_end();
+    let first_word = code
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if matches!(
+        &*first_word,
+        "endif" | "endforeach" | "endfor" | "endwhile" | "endswitch" | "enddeclare"
+    ) || code == "}"
+    {
+        TemplateTagKind::Close
+    } else if code.starts_with('}') && code.ends_with('{')
+        || code.ends_with(':') && matches!(&*first_word, "else" | "elseif" | "case" | "default")
+    {
+        TemplateTagKind::Middle
+    } else if code.ends_with('{') && !code.contains('}')
+        || code.ends_with(':')
+            && matches!(
+                &*first_word,
+                "if" | "foreach" | "for" | "while" | "switch" | "declare"
+            )
+    {
+        TemplateTagKind::Open
+    } else {
+        TemplateTagKind::Other
+    }
+}
+
+/// Strip Go template trim markers `- ` and ` -` from both sides of action.
+/// The marker must be separated by whitespace, otherwise it's a negative number like `{{-3}}`.
+pub(crate) fn strip_go_template_trim_markers(action: &str) -> (&str, bool, bool) {
+    let (action, trim_prev) = action
+        .strip_prefix('-')
+        .filter(|rest| rest.starts_with(|c: char| c.is_ascii_whitespace()))
+        .map(|action| (action, true))
+        .unwrap_or((action, false));
+    let (action, trim_next) = action
+        .strip_suffix('-')
+        .filter(|rest| rest.ends_with(|c: char| c.is_ascii_whitespace()))
+        .map(|action| (action, true))
+        .unwrap_or((action, false));
+    (action, trim_prev, trim_next)
+}
+
+/// Strip Handlebars whitespace control markers `~` from both sides of tag.
+pub(crate) fn strip_handlebars_trim_markers(tag: &str) -> (&str, bool, bool) {
+    let (tag, trim_prev) = tag
+        .strip_prefix('~')
+        .map(|tag| (tag, true))
+        .unwrap_or((tag, false));
+    let (tag, trim_next) = tag
+        .strip_suffix('~')
+        .map(|tag| (tag, true))
+        .unwrap_or((tag, false));
+    (tag, trim_prev, trim_next)
+}
+
+/// Get block name of Handlebars block tag after the leading `#`, `^` or `/`,
+/// which may be prefixed with `>` (partial block) or `*` (decorator block).
+pub(crate) fn parse_handlebars_block_name(tag: &str) -> &str {
+    let tag = tag.trim_start().trim_start_matches(['>', '*']).trim_start();
+    tag.split(|c: char| c.is_ascii_whitespace() || c == '~')
+        .next()
+        .unwrap_or(tag)
+}
+
+/// Liquid tags whose body isn't Liquid or HTML, so it should be kept as raw text.
+pub(crate) fn is_liquid_raw_block(tag_name: &str) -> bool {
+    matches!(
+        tag_name,
+        "comment" | "raw" | "schema" | "javascript" | "stylesheet"
+    )
+}
+
+pub(crate) fn has_top_level_comma(code: &str) -> bool {
+    find_top_level(code, |_, c| c == ',').is_some()
+}
+
+/// Find the first char that isn't inside quotes or brackets and satisfies the predicate.
+pub(crate) fn find_top_level(
+    code: &str,
+    mut predicate: impl FnMut(usize, char) -> bool,
+) -> Option<usize> {
+    let mut pair_stack = vec![];
+    let mut chars = code.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                if pair_stack.last().is_some_and(|last| *last == c) {
+                    pair_stack.pop();
+                } else if !matches!(pair_stack.last(), Some('\'' | '"' | '`')) {
+                    pair_stack.push(c);
+                }
+            }
+            '\\' if matches!(pair_stack.last(), Some('\'' | '"' | '`')) => {
+                chars.next();
+            }
+            '(' | '[' | '{' if !matches!(pair_stack.last(), Some('\'' | '"' | '`')) => {
+                pair_stack.push(c);
+            }
+            ')' | ']' | '}'
+                if !pair_stack.is_empty()
+                    && !matches!(pair_stack.last(), Some('\'' | '"' | '`')) =>
+            {
+                pair_stack.pop();
+            }
+            _ if pair_stack.is_empty() && predicate(i, c) => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
 pub(crate) static UNESCAPING_AC: LazyLock<AhoCorasick> =
     LazyLock::new(|| AhoCorasick::new(["&quot;", "&#x22;", "&#x27;"]).unwrap());
@@ -11,7 +11,7 @@ mod state;
 
 use crate::{config::FormatOptions, ctx::Ctx, parser::Parser, printer::DocGen, state::State};
 pub use crate::{ctx::Hints, error::*, parser::Language};
-use std::{borrow::Cow, path::Path};
+use std::{borrow::Cow, ops::Range, path::Path};
 use tiny_pretty::{IndentKind, PrintOptions};
 
 /// Format the given source code.
@@ -55,7 +55,11 @@ pub fn format_text<E, F>(
 where
     F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
 {
-    let mut parser = Parser::new(code, language.clone());
+    let language = match language {
+        Language::Html if options.language.html_go_template => Language::GoTemplate,
+        language => language,
+    };
+    let mut parser = Parser::new(code, language.clone(), &options.language);
     let ast = parser.parse_root().map_err(FormatError::Syntax)?;
 
     if ast.children.first().is_some_and(|child| {
@@ -114,6 +118,172 @@ where
     ))
 }
 
+/// Placeholders of host language expressions inside a markup fragment,
+/// such as `${expr}` in tagged template literals of Lit.
+#[derive(Clone, Debug)]
+pub enum Placeholders<'a> {
+    /// Byte ranges of placeholders in source code.
+    Ranges(&'a [Range<usize>]),
+    /// Pattern of placeholder tokens that already exist in source code,
+    /// where `{}` stands for a sequence of ASCII digits, like `PLACEHOLDER_{}_`.
+    Pattern(&'a str),
+}
+
+/// Format a markup fragment that contains opaque placeholders.
+///
+/// Each placeholder is treated as an atomic unit: it can appear as text,
+/// a whole node, an attribute name or an attribute value,
+/// and it will be kept verbatim in the formatted output.
+/// This is useful for formatting templates embedded in other languages.
+///
+/// ```
+/// use markup_fmt::{format_fragment, Language, Placeholders};
+///
+/// let code = "<div class=${cls}   @click=${onClick}>${content}</div>";
+/// let ranges = [11..17, 27..37, 38..48];
+///
+/// let formatted = format_fragment(
+///     code,
+///     Placeholders::Ranges(&ranges),
+///     Language::Html,
+///     &Default::default(),
+///     |code, _| Ok::<_, std::convert::Infallible>(code.into()),
+/// ).unwrap();
+/// assert_eq!(formatted, "<div class=\"${cls}\" @click=\"${onClick}\">${content}</div>\n");
+///
+/// let code = "<ul>  <li  PH_0_>PH_1_</li><PH_2_></PH_2_></ul>";
+/// let formatted = format_fragment(
+///     code,
+///     Placeholders::Pattern("PH_{}_"),
+///     Language::Html,
+///     &Default::default(),
+///     |code, _| Ok::<_, std::convert::Infallible>(code.into()),
+/// ).unwrap();
+/// assert_eq!(formatted, "<ul>\n  <li PH_0_>PH_1_</li>\n  <PH_2_></PH_2_>\n</ul>\n");
+/// ```
+///
+/// # Errors
+///
+/// Besides errors of [`format_text`], it returns [`FormatError::InvalidPlaceholder`]
+/// if placeholder ranges overlap, or they aren't valid byte ranges of the source code.
+pub fn format_fragment<E, F>(
+    code: &str,
+    placeholders: Placeholders,
+    language: Language,
+    options: &FormatOptions,
+    external_formatter: F,
+) -> Result<String, FormatError<E>>
+where
+    F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+{
+    let mut ranges = match placeholders {
+        Placeholders::Ranges(ranges) => ranges.to_vec(),
+        Placeholders::Pattern(pattern) => find_placeholders(code, pattern),
+    };
+    ranges.sort_by_key(|range| range.start);
+    if ranges.is_empty() {
+        return format_text(code, language, options, external_formatter);
+    }
+
+    // Replacement tokens must be valid as tag names, attribute names,
+    // unquoted attribute values, JavaScript identifiers and CSS identifiers.
+    // They consist of a prefix which doesn't exist in source code, an index and trailing underscores,
+    // so a token can't be found inside another token, like `_1_` inside `_10_`.
+    let prefix = find_placeholder_prefix(code);
+
+    // Identical placeholders share the same token,
+    // so that placeholders as tag names can match their closing tags.
+    let mut tokens = Vec::<(&str, String)>::new();
+    let mut replaced = String::with_capacity(code.len());
+    let mut last = 0;
+    for range in &ranges {
+        let original = code
+            .get(range.clone())
+            .filter(|_| range.start >= last)
+            .ok_or_else(|| FormatError::InvalidPlaceholder(range.clone()))?;
+        let token =
+            if let Some((_, token)) = tokens.iter().find(|(existing, _)| *existing == original) {
+                token
+            } else {
+                // Keep the same width as the original if possible,
+                // so line breaks are decided as if placeholders were there.
+                let mut token = format!("{prefix}{}_", tokens.len());
+                let width = original.chars().count();
+                if token.len() < width {
+                    token.push_str(&"_".repeat(width - token.len()));
+                }
+                tokens.push((original, token));
+                &tokens[tokens.len() - 1].1
+            };
+        replaced.push_str(&code[last..range.start]);
+        replaced.push_str(token);
+        last = range.end;
+    }
+    replaced.push_str(&code[last..]);
+
+    let mut formatted = format_text(&replaced, language, options, external_formatter)?;
+    for (original, token) in &tokens {
+        formatted = formatted.replace(token, original);
+    }
+    Ok(formatted)
+}
+
+/// Find the shortest prefix of lowercase letters that doesn't exist in code,
+/// regardless of case since tag names may be normalized.
+fn find_placeholder_prefix(code: &str) -> String {
+    // uncommon letters go first
+    const LETTERS: &[u8] = b"zqxjkvbpygfwmucldrhsnioate";
+    let code = code.to_ascii_lowercase();
+    let mut len = 2;
+    loop {
+        let mut indexes = vec![0; len];
+        loop {
+            let prefix = indexes
+                .iter()
+                .map(|i| LETTERS[*i] as char)
+                .collect::<String>();
+            if !code.contains(&prefix) {
+                return prefix;
+            }
+            let Some(pos) = indexes.iter().rposition(|i| *i + 1 < LETTERS.len()) else {
+                break;
+            };
+            indexes[pos] += 1;
+            indexes[pos + 1..].fill(0);
+        }
+        len += 1;
+    }
+}
+
+fn find_placeholders(code: &str, pattern: &str) -> Vec<Range<usize>> {
+    let Some((prefix, suffix)) = pattern.split_once("{}") else {
+        return code
+            .match_indices(pattern)
+            .filter(|_| !pattern.is_empty())
+            .map(|(start, matched)| start..start + matched.len())
+            .collect();
+    };
+    let mut ranges = vec![];
+    let mut offset = 0;
+    while let Some(start) = code[offset..].find(prefix).map(|i| offset + i) {
+        let digits_start = start + prefix.len();
+        let digits_end = code[digits_start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(code.len(), |i| digits_start + i);
+        if digits_end > digits_start && code[digits_end..].starts_with(suffix) {
+            let end = digits_end + suffix.len();
+            ranges.push(start..end);
+            offset = end;
+        } else {
+            offset = start + prefix.len().max(1);
+            while !code.is_char_boundary(offset) {
+                offset += 1;
+            }
+        }
+    }
+    ranges
+}
+
 /// Detect language from file extension.
 pub fn detect_language(path: impl AsRef<Path>) -> Option<Language> {
     let path = path.as_ref();
@@ -132,8 +302,25 @@ pub fn detect_language(path: impl AsRef<Path>) -> Option<Language> {
         Some("vue") => Some(Language::Vue),
         Some("svelte") => Some(Language::Svelte),
         Some("astro") => Some(Language::Astro),
-        Some("jinja" | "jinja2" | "twig" | "njk") => Some(Language::Jinja),
+        Some("jinja" | "jinja2" | "twig" | "njk" | "tera" | "askama") => Some(Language::Jinja),
         Some("vto") => Some(Language::Vento),
+        Some("liquid") => Some(Language::Liquid),
+        Some("gohtml" | "tmpl") => Some(Language::GoTemplate),
+        Some("erb") => Some(Language::Erb),
+        Some("eex") => Some(Language::Eex),
+        Some("heex") => Some(Language::Heex),
+        Some("php")
+            if path
+                .file_stem()
+                .is_some_and(|file_stem| file_stem.to_string_lossy().ends_with(".blade")) =>
+        {
+            Some(Language::Blade)
+        }
+        Some("php" | "phtml") => Some(Language::Php),
+        Some("cshtml" | "razor") => Some(Language::Razor),
+        Some("xml" | "svg" | "xhtml" | "plist" | "csproj") => Some(Language::Xml),
+        Some("mjml") => Some(Language::Mjml),
+        Some("hbs" | "handlebars" | "mustache") => Some(Language::Handlebars),
         _ => None,
     }
 }
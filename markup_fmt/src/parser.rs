@@ -9,6 +9,7 @@
 
 use crate::{
     ast::*,
+    config::{LanguageOptions, VentoTagKind},
     error::{SyntaxError, SyntaxErrorKind},
     helpers,
 };
@@ -24,11 +25,23 @@ pub enum Language {
     Angular,
     Jinja,
     Vento,
+    Handlebars,
+    Liquid,
+    GoTemplate,
+    Erb,
+    Eex,
+    Heex,
+    Blade,
+    Php,
+    Razor,
+    Xml,
+    Mjml,
 }
 
 pub struct Parser<'s> {
     source: &'s str,
     language: Language,
+    options: &'s LanguageOptions,
     chars: Peekable<CharIndices<'s>>,
     state: ParserState,
 }
@@ -36,13 +49,15 @@ pub struct Parser<'s> {
 #[derive(Default)]
 struct ParserState {
     has_front_matter: bool,
+    in_svg: bool,
 }
 
 impl<'s> Parser<'s> {
-    pub fn new(source: &'s str, language: Language) -> Self {
+    pub fn new(source: &'s str, language: Language, options: &'s LanguageOptions) -> Self {
         Self {
             source,
             language,
+            options,
             chars: source.char_indices().peekable(),
             state: Default::default(),
         }
@@ -680,20 +695,28 @@ impl<'s> Parser<'s> {
 
     fn parse_attr(&mut self) -> PResult<Attribute<'s>> {
         match self.language {
-            Language::Html | Language::Angular => self.parse_native_attr().map(Attribute::Native),
+            Language::Html
+            | Language::Angular
+            | Language::Razor
+            | Language::Xml
+            | Language::Mjml => self.parse_native_attr().map(Attribute::Native),
             Language::Vue => self
                 .try_parse(Parser::parse_vue_directive)
                 .map(Attribute::VueDirective)
                 .or_else(|_| self.parse_native_attr().map(Attribute::Native)),
             Language::Svelte => self
-                .try_parse(Parser::parse_svelte_attr)
-                .map(Attribute::Svelte)
+                .try_parse(Parser::parse_svelte_attachment)
+                .map(Attribute::SvelteAttachment)
+                .or_else(|_| {
+                    self.try_parse(Parser::parse_svelte_attr)
+                        .map(Attribute::Svelte)
+                })
                 .or_else(|_| self.parse_native_attr().map(Attribute::Native)),
             Language::Astro => self
                 .try_parse(Parser::parse_astro_attr)
                 .map(Attribute::Astro)
                 .or_else(|_| self.parse_native_attr().map(Attribute::Native)),
-            Language::Jinja => {
+            Language::Jinja | Language::Liquid => {
                 self.skip_ws();
                 let result = if matches!(self.chars.peek(), Some((_, '{'))) {
                     let mut chars = self.chars.clone();
@@ -715,11 +738,75 @@ impl<'s> Parser<'s> {
                 .try_parse(|parser| parser.parse_vento_tag_or_block(None))
                 .map(Attribute::VentoTagOrBlock)
                 .or_else(|_| self.parse_native_attr().map(Attribute::Native)),
+            Language::Blade => {
+                let source = self.source;
+                if self
+                    .chars
+                    .peek()
+                    .is_some_and(|(i, _)| parse_blade_directive_name_at(source, *i).is_some())
+                {
+                    self.parse_blade_directive_or_block(None)
+                        .map(Attribute::BladeDirectiveOrBlock)
+                } else {
+                    self.parse_native_attr().map(Attribute::Native)
+                }
+            }
+            Language::Php => {
+                let source = self.source;
+                if self
+                    .chars
+                    .peek()
+                    .is_some_and(|(i, _)| helpers::is_php_tag_start(&source[*i..]))
+                {
+                    self.parse_php_tag_or_block(None)
+                        .map(Attribute::PhpTagOrBlock)
+                } else {
+                    self.parse_native_attr().map(Attribute::Native)
+                }
+            }
+            Language::Erb | Language::Eex | Language::Heex => self
+                .try_parse(|parser| parser.parse_erb_tag_or_block(None))
+                .map(Attribute::ErbTagOrBlock)
+                .or_else(|_| {
+                    if matches!(self.language, Language::Heex) {
+                        self.try_parse(Parser::parse_heex_braced)
+                            .map(|name| {
+                                Attribute::Native(NativeAttribute {
+                                    name,
+                                    value: None,
+                                    quote: None,
+                                })
+                            })
+                            .or_else(|_| self.parse_native_attr().map(Attribute::Native))
+                    } else {
+                        self.parse_native_attr().map(Attribute::Native)
+                    }
+                }),
+            Language::GoTemplate => self
+                .try_parse(|parser| parser.parse_go_template_tag_or_block(None))
+                .map(Attribute::GoTemplateTagOrBlock)
+                .or_else(|_| self.parse_native_attr().map(Attribute::Native)),
+            Language::Handlebars => self
+                .try_parse(|parser| parser.parse_handlebars_tag_or_block(None))
+                .map(Attribute::HandlebarsTagOrBlock)
+                .or_else(|_| {
+                    self.try_parse(Parser::parse_handlebars_block_params)
+                        .map(Attribute::Native)
+                })
+                .or_else(|_| self.parse_native_attr().map(Attribute::Native)),
         }
     }
 
     fn parse_attr_name(&mut self) -> PResult<&'s str> {
-        if matches!(self.language, Language::Jinja | Language::Vento) {
+        if matches!(
+            self.language,
+            Language::Jinja
+                | Language::Vento
+                | Language::Handlebars
+                | Language::Liquid
+                | Language::GoTemplate
+                | Language::Blade
+        ) {
             let Some((start, mut end)) = (match self.chars.peek() {
                 Some((i, '{')) => {
                     let start = *i;
@@ -787,12 +874,45 @@ impl<'s> Parser<'s> {
         let quote = self.chars.next_if(|(_, c)| *c == '"' || *c == '\'');
 
         if let Some((start, quote)) = quote {
-            let is_jinja_or_vento = matches!(self.language, Language::Jinja | Language::Vento);
+            let is_jinja_or_vento = matches!(
+                self.language,
+                Language::Jinja
+                    | Language::Vento
+                    | Language::Handlebars
+                    | Language::Liquid
+                    | Language::GoTemplate
+                    | Language::Blade
+            );
+            let is_erb = matches!(
+                self.language,
+                Language::Erb | Language::Eex | Language::Heex
+            );
+            let is_php = matches!(self.language, Language::Php);
+            let is_razor = matches!(self.language, Language::Razor);
             let start = start + 1;
             let mut end = start;
             let mut chars_stack = vec![];
             loop {
                 match self.chars.next() {
+                    Some((i, '<'))
+                        if is_erb && self.chars.next_if(|(_, c)| *c == '%').is_some() =>
+                    {
+                        // quotes inside ERB tag don't terminate attribute value
+                        if let Some(index) = self.source.get(i..).and_then(|rest| rest.find("%>")) {
+                            while self.chars.next_if(|(j, _)| *j < i + index + 2).is_some() {}
+                        }
+                    }
+                    Some((i, '<')) if is_php && helpers::is_php_tag_start(&self.source[i..]) => {
+                        // quotes inside PHP tag don't terminate attribute value
+                        let end = find_php_tag_end(&self.source[i..])
+                            .map_or(self.source.len(), |index| i + index + "?>".len());
+                        while self.chars.next_if(|(j, _)| *j < end).is_some() {}
+                    }
+                    Some((i, '@')) if is_razor && is_razor_transition(self.source, i) => {
+                        // quotes inside Razor expression don't terminate attribute value
+                        let end = i + 1 + razor_expr_len(&self.source[i + 1..]);
+                        while self.chars.next_if(|(j, _)| *j < end).is_some() {}
+                    }
                     Some((i, c)) if c == quote => {
                         if chars_stack.is_empty() || !is_jinja_or_vento {
                             end = i;
@@ -817,6 +937,11 @@ impl<'s> Parser<'s> {
                 }
             }
             Ok((unsafe { self.source.get_unchecked(start..end) }, start))
+        } else if matches!(self.language, Language::Heex)
+            && matches!(self.chars.peek(), Some((_, '{')))
+        {
+            let start = self.chars.peek().map(|(i, _)| *i).unwrap_or_default();
+            self.parse_heex_braced().map(|value| (value, start))
         } else {
             fn is_unquoted_attr_value_char(c: char) -> bool {
                 !c.is_ascii_whitespace() && !matches!(c, '"' | '\'' | '=' | '<' | '>' | '`')
@@ -831,7 +956,15 @@ impl<'s> Parser<'s> {
             loop {
                 match self.chars.peek() {
                     Some((i, '{'))
-                        if matches!(self.language, Language::Jinja | Language::Vento) =>
+                        if matches!(
+                            self.language,
+                            Language::Jinja
+                                | Language::Vento
+                                | Language::Handlebars
+                                | Language::Liquid
+                                | Language::GoTemplate
+                                | Language::Blade
+                        ) =>
                     {
                         end = *i;
                         let mut chars = self.chars.clone();
@@ -856,6 +989,240 @@ impl<'s> Parser<'s> {
         }
     }
 
+    fn parse_blade_block_children(&mut self) -> PResult<Vec<Node<'s>>> {
+        let mut children = vec![];
+        loop {
+            match self.chars.peek() {
+                Some((i, '@')) if parse_blade_directive_name_at(self.source, *i).is_some() => break,
+                Some(..) => {
+                    children.push(self.parse_node()?);
+                }
+                None => return Err(self.emit_error(SyntaxErrorKind::ExpectBladeBlockEnd)),
+            }
+        }
+        Ok(children)
+    }
+
+    /// Parse Blade directive like `@include('view')`, without its body.
+    fn parse_blade_directive(&mut self) -> PResult<BladeDirective<'s>> {
+        let Some((start, _)) = self.chars.next_if(|(_, c)| *c == '@') else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectBladeDirective));
+        };
+        let name_start = start + 1;
+        let mut name_end = name_start;
+        while let Some((i, _)) = self
+            .chars
+            .next_if(|(_, c)| c.is_ascii_alphanumeric() || *c == '_')
+        {
+            name_end = i + 1;
+        }
+        if name_end == name_start {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectBladeDirective));
+        }
+        let name = unsafe { self.source.get_unchecked(name_start..name_end) };
+
+        let mut chars = self.chars.clone();
+        while chars.next_if(|(_, c)| *c == ' ' || *c == '\t').is_some() {}
+        let Some((paren, _)) = chars.next_if(|(_, c)| *c == '(') else {
+            return Ok(BladeDirective { name, args: None });
+        };
+        self.chars = chars;
+        let args_start = paren + 1;
+        let mut parens_stack = 0usize;
+        let mut quote = None;
+        let args_end = loop {
+            match self.chars.next() {
+                Some((_, c)) if quote.is_some_and(|q| q == c) => quote = None,
+                Some((_, '\\')) if quote.is_some() => {
+                    self.chars.next();
+                }
+                Some(..) if quote.is_some() => {}
+                Some((_, c @ ('"' | '\''))) => quote = Some(c),
+                Some((_, '(')) => parens_stack += 1,
+                Some((i, ')')) => {
+                    if parens_stack == 0 {
+                        break i;
+                    }
+                    parens_stack -= 1;
+                }
+                Some(..) => {}
+                None => return Err(self.emit_error(SyntaxErrorKind::ExpectChar(')'))),
+            }
+        };
+        Ok(BladeDirective {
+            name,
+            args: Some((
+                unsafe { self.source.get_unchecked(args_start..args_end) },
+                args_start,
+            )),
+        })
+    }
+
+    fn parse_blade_directive_or_block(
+        &mut self,
+        first_directive: Option<BladeDirective<'s>>,
+    ) -> PResult<NodeKind<'s>> {
+        let first_directive = if let Some(first_directive) = first_directive {
+            first_directive
+        } else {
+            self.parse_blade_directive()?
+        };
+        let name = first_directive.name;
+
+        if matches!(name, "php" | "verbatim") && first_directive.args.is_none() {
+            // body of `@php` and `@verbatim` isn't Blade template
+            let start = self
+                .chars
+                .peek()
+                .map(|(i, _)| *i)
+                .unwrap_or(self.source.len());
+            let Some(end) =
+                find_blade_end_directive(&self.source[start..], name).map(|index| start + index)
+            else {
+                return Err(self.emit_error(SyntaxErrorKind::ExpectBladeBlockEnd));
+            };
+            while self.chars.next_if(|(i, _)| *i < end).is_some() {}
+            let raw = unsafe { self.source.get_unchecked(start..end) };
+            let mut body = vec![BladeDirectiveOrChildren::Directive(first_directive)];
+            if !raw.is_empty() {
+                body.push(BladeDirectiveOrChildren::Children(vec![Node {
+                    kind: NodeKind::Text(TextNode {
+                        raw,
+                        line_breaks: raw.chars().filter(|c| *c == '\n').count(),
+                        start,
+                    }),
+                    raw,
+                }]));
+            }
+            body.push(BladeDirectiveOrChildren::Directive(
+                self.parse_blade_directive()?,
+            ));
+            return Ok(NodeKind::BladeBlock(BladeBlock { body }));
+        }
+
+        let is_block = match name {
+            // `@section('title', 'Value')` is a short form without body
+            "section" | "slot" | "push" | "prepend" => first_directive
+                .args
+                .is_some_and(|(args, _)| !helpers::has_top_level_comma(args)),
+            // `@php($a = 1)` is inline, even if there're `@php` blocks after it
+            "php" => false,
+            _ => self
+                .chars
+                .peek()
+                .is_some_and(|(i, _)| find_blade_end_directive(&self.source[*i..], name).is_some()),
+        };
+        if !is_block {
+            return Ok(NodeKind::BladeDirective(first_directive));
+        }
+
+        let mut body = vec![BladeDirectiveOrChildren::Directive(first_directive)];
+        loop {
+            let mut children = self.parse_blade_block_children()?;
+            if !children.is_empty() {
+                if let Some(BladeDirectiveOrChildren::Children(nodes)) = body.last_mut() {
+                    nodes.append(&mut children);
+                } else {
+                    body.push(BladeDirectiveOrChildren::Children(children));
+                }
+            }
+            let next_directive = self.parse_blade_directive()?;
+            let next_name = next_directive.name;
+            if next_name.strip_prefix("end") == Some(name)
+                || name == "section"
+                    && matches!(next_name, "show" | "stop" | "overwrite" | "append")
+            {
+                body.push(BladeDirectiveOrChildren::Directive(next_directive));
+                break;
+            }
+            let is_branch = match name {
+                "forelse" => next_name == "empty",
+                "switch" => matches!(next_name, "case" | "default"),
+                "foreach" | "for" | "while" | "section" | "slot" | "push" | "prepend" | "once" => {
+                    false
+                }
+                _ => next_name.starts_with("else"),
+            };
+            if is_branch {
+                body.push(BladeDirectiveOrChildren::Directive(next_directive));
+            } else {
+                let node = self
+                    .with_taken(|parser| {
+                        parser.parse_blade_directive_or_block(Some(next_directive))
+                    })
+                    .map(|(kind, raw)| Node { kind, raw })?;
+                if let Some(BladeDirectiveOrChildren::Children(nodes)) = body.last_mut() {
+                    nodes.push(node);
+                } else {
+                    body.push(BladeDirectiveOrChildren::Children(vec![node]));
+                }
+            }
+        }
+        Ok(NodeKind::BladeBlock(BladeBlock { body }))
+    }
+
+    /// Parse `{{ }}`, `{!! !!}` or `{{-- --}}`.
+    fn parse_blade_echo_or_comment(&mut self) -> PResult<NodeKind<'s>> {
+        let Some(start) = self.chars.peek().map(|(i, _)| *i) else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectBladeEcho));
+        };
+        let rest = &self.source[start..];
+        let (open, close) = if rest.starts_with("{{--") {
+            ("{{--", "--}}")
+        } else if rest.starts_with("{{") {
+            ("{{", "}}")
+        } else if rest.starts_with("{!!") {
+            ("{!!", "!!}")
+        } else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectBladeEcho));
+        };
+        let Some(index) = rest[open.len()..].find(close) else {
+            return Err(self.emit_error(if open == "{{--" {
+                SyntaxErrorKind::ExpectBladeComment
+            } else {
+                SyntaxErrorKind::ExpectBladeEcho
+            }));
+        };
+        let content_start = start + open.len();
+        let content_end = content_start + index;
+        while self
+            .chars
+            .next_if(|(i, _)| *i < content_end + close.len())
+            .is_some()
+        {}
+
+        let content = unsafe { self.source.get_unchecked(content_start..content_end) };
+        Ok(match open {
+            "{{--" => NodeKind::BladeComment(BladeComment { raw: content }),
+            _ => NodeKind::BladeEcho(BladeEcho {
+                expr: content,
+                escaped: open == "{{",
+            }),
+        })
+    }
+
+    fn parse_cdata(&mut self) -> PResult<Cdata<'s>> {
+        let Some(start) = self
+            .chars
+            .peek()
+            .map(|(i, _)| *i + "<![CDATA[".len())
+            .filter(|start| self.source[..*start].ends_with("<![CDATA["))
+        else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectCdata));
+        };
+        let Some(end) = self.source[start..].find("]]>").map(|i| start + i) else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectCdata));
+        };
+        while self
+            .chars
+            .next_if(|(i, _)| *i < end + "]]>".len())
+            .is_some()
+        {}
+        Ok(Cdata {
+            raw: unsafe { self.source.get_unchecked(start..end) },
+        })
+    }
+
     fn parse_comment(&mut self) -> PResult<Comment<'s>> {
         let Some((start, _)) = self
             .chars
@@ -990,14 +1357,51 @@ impl<'s> Parser<'s> {
             }
         }
 
+        let is_non_compiled = helpers::has_non_compiled_attr(&attrs, &self.language);
+        // There're no raw text elements in XML.
+        let is_raw_text = is_non_compiled
+            || !self.state.in_svg && helpers::is_raw_text_element(tag_name, self.language.clone())
+            || match self.language {
+                Language::Xml => false,
+                Language::Mjml if tag_name.starts_with("mj-") => matches!(
+                    helpers::get_mjml_content(tag_name),
+                    Some(helpers::MjmlContent::Raw | helpers::MjmlContent::Css)
+                ),
+                _ => {
+                    tag_name.eq_ignore_ascii_case("script")
+                        || tag_name.eq_ignore_ascii_case("style")
+                        || tag_name.eq_ignore_ascii_case("pre")
+                        || tag_name.eq_ignore_ascii_case("textarea")
+                }
+            };
+        let was_in_svg = self.state.in_svg;
+        if tag_name.eq_ignore_ascii_case("svg") {
+            self.state.in_svg = true;
+        }
+        let children_start = self
+            .chars
+            .peek()
+            .map(|(i, _)| *i)
+            .unwrap_or(self.source.len());
         let mut children = vec![];
-        if tag_name.eq_ignore_ascii_case("script")
-            || tag_name.eq_ignore_ascii_case("style")
-            || tag_name.eq_ignore_ascii_case("pre")
-            || tag_name.eq_ignore_ascii_case("textarea")
-        {
-            let text_node = self.parse_raw_text_node(tag_name)?;
+        if is_raw_text {
+            let text_node = self.parse_raw_text_node(tag_name, is_non_compiled)?;
             let raw = text_node.raw;
+            if self.chars.peek().is_none() && tag_name.eq_ignore_ascii_case("plaintext") {
+                // `plaintext` element without end tag takes all the rest as its text content.
+                self.state.in_svg = was_in_svg;
+                return Ok(Element {
+                    tag_name,
+                    attrs,
+                    first_attr_same_line,
+                    children: vec![Node {
+                        kind: NodeKind::Text(text_node),
+                        raw,
+                    }],
+                    self_closing: false,
+                    void_element,
+                });
+            }
             if !raw.is_empty() {
                 children.push(Node {
                     kind: NodeKind::Text(text_node),
@@ -1014,13 +1418,35 @@ impl<'s> Parser<'s> {
                     if let Some((pos, _)) = chars.next_if(|(_, c)| *c == '/') {
                         self.chars = chars;
                         let close_tag_name = self.parse_tag_name()?;
-                        if !close_tag_name.eq_ignore_ascii_case(tag_name) {
+                        let is_matched = if matches!(self.language, Language::Xml) {
+                            close_tag_name == tag_name
+                        } else {
+                            close_tag_name.eq_ignore_ascii_case(tag_name)
+                        };
+                        if !is_matched {
                             return Err(
                                 self.emit_error_with_pos(SyntaxErrorKind::ExpectCloseTag, pos)
                             );
                         }
                         self.skip_ws();
                         if self.chars.next_if(|(_, c)| *c == '>').is_some() {
+                            // Text content in XML is data, so it's kept as is
+                            // with the elements around it.
+                            if matches!(self.language, Language::Xml)
+                                && children.iter().any(|child| {
+                                    matches!(&child.kind, NodeKind::Text(text_node) if !text_node.raw.trim().is_empty())
+                                })
+                            {
+                                let raw = unsafe { self.source.get_unchecked(children_start..pos - 1) };
+                                children = vec![Node {
+                                    kind: NodeKind::Text(TextNode {
+                                        raw,
+                                        line_breaks: raw.chars().filter(|c| *c == '\n').count(),
+                                        start: children_start,
+                                    }),
+                                    raw,
+                                }];
+                            }
                             break;
                         }
                         return Err(self.emit_error(SyntaxErrorKind::ExpectCloseTag));
@@ -1028,12 +1454,8 @@ impl<'s> Parser<'s> {
                     children.push(self.parse_node()?);
                 }
                 Some(..) => {
-                    if tag_name.eq_ignore_ascii_case("script")
-                        || tag_name.eq_ignore_ascii_case("style")
-                        || tag_name.eq_ignore_ascii_case("pre")
-                        || tag_name.eq_ignore_ascii_case("textarea")
-                    {
-                        let text_node = self.parse_raw_text_node(tag_name)?;
+                    if is_raw_text {
+                        let text_node = self.parse_raw_text_node(tag_name, is_non_compiled)?;
                         let raw = text_node.raw;
                         if !raw.is_empty() {
                             children.push(Node {
@@ -1048,6 +1470,7 @@ impl<'s> Parser<'s> {
                 None => return Err(self.emit_error(SyntaxErrorKind::ExpectCloseTag)),
             }
         }
+        self.state.in_svg = was_in_svg;
 
         Ok(Element {
             tag_name,
@@ -1060,16 +1483,47 @@ impl<'s> Parser<'s> {
     }
 
     fn parse_front_matter(&mut self) -> PResult<FrontMatter<'s>> {
+        let delimiter = match self.chars.peek() {
+            Some((_, '+')) if !matches!(self.language, Language::Astro) => '+',
+            _ => '-',
+        };
         let Some((start, _)) = self
             .chars
-            .next_if(|(_, c)| *c == '-')
-            .and_then(|_| self.chars.next_if(|(_, c)| *c == '-'))
-            .and_then(|_| self.chars.next_if(|(_, c)| *c == '-'))
+            .next_if(|(_, c)| *c == delimiter)
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == delimiter))
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == delimiter))
         else {
             return Err(self.emit_error(SyntaxErrorKind::ExpectFrontMatter));
         };
         let start = start + 1;
 
+        if !matches!(self.language, Language::Astro) {
+            // YAML or TOML front matter ends with delimiter line.
+            let delimiter = if delimiter == '+' { "+++" } else { "---" };
+            let Some(end) = self.source[start..]
+                .match_indices(delimiter)
+                .map(|(i, _)| start + i)
+                .find(|i| {
+                    self.source[..*i].ends_with('\n')
+                        && self.source[i + 3..]
+                            .trim_start_matches([' ', '\t'])
+                            .split('\n')
+                            .next()
+                            .is_some_and(|rest| rest.trim_end_matches('\r').is_empty())
+                })
+            else {
+                return Err(self.emit_error(SyntaxErrorKind::ExpectFrontMatter));
+            };
+            while self.chars.next_if(|(i, _)| *i < end + 3).is_some() {}
+
+            self.state.has_front_matter = true;
+            return Ok(FrontMatter {
+                raw: unsafe { self.source.get_unchecked(start..end) },
+                start,
+                toml: delimiter == "+++",
+            });
+        }
+
         let mut pair_stack = vec![];
         let mut end = start;
         loop {
@@ -1136,6 +1590,7 @@ impl<'s> Parser<'s> {
         Ok(FrontMatter {
             raw: unsafe { self.source.get_unchecked(start..end) },
             start,
+            toml: false,
         })
     }
 
@@ -1283,23 +1738,46 @@ impl<'s> Parser<'s> {
             self.parse_jinja_tag()?
         };
         let tag_name = parse_jinja_tag_name(&first_tag);
+        let is_liquid = matches!(self.language, Language::Liquid);
 
-        if matches!(
-            tag_name,
-            "for"
-                | "if"
-                | "macro"
-                | "call"
-                | "filter"
-                | "block"
-                | "apply"
-                | "autoescape"
-                | "embed"
-                | "with"
-                | "set"
-                | "trans"
-                | "raw"
-        ) {
+        if is_liquid && helpers::is_liquid_raw_block(tag_name) {
+            return self
+                .parse_liquid_raw_block_rest(first_tag, tag_name)
+                .map(T::from_block);
+        }
+
+        let is_block = if is_liquid {
+            matches!(
+                tag_name,
+                "if" | "unless"
+                    | "case"
+                    | "for"
+                    | "tablerow"
+                    | "capture"
+                    | "form"
+                    | "paginate"
+                    | "style"
+            )
+        } else {
+            match tag_name {
+                "for" | "if" | "macro" | "filter" | "block" | "apply" | "autoescape" | "embed"
+                | "with" | "trans" | "raw" | "match" => true,
+                // `{% set x = 1 %}` and Askama `{% let x = 1 %}` don't have body
+                "set" => !first_tag.content.contains('='),
+                "let" => {
+                    !first_tag.content.contains('=')
+                        && self.chars.peek().is_some_and(|(i, _)| {
+                            find_jinja_end_tag(&self.source[*i..], tag_name).is_some()
+                        })
+                }
+                // Askama `{% call %}` may be used without `{% endcall %}`
+                "call" => self.chars.peek().is_some_and(|(i, _)| {
+                    find_jinja_end_tag(&self.source[*i..], tag_name).is_some()
+                }),
+                _ => false,
+            }
+        };
+        if is_block {
             let mut body = vec![JinjaTagOrChildren::Tag(first_tag)];
 
             loop {
@@ -1321,9 +1799,20 @@ impl<'s> Parser<'s> {
                         body.push(JinjaTagOrChildren::Tag(next_tag));
                         break;
                     }
-                    if (tag_name == "if" || tag_name == "for")
-                        && matches!(next_tag_name, "elif" | "elseif" | "else")
-                    {
+                    let is_branch = if is_liquid {
+                        matches!(
+                            (tag_name, next_tag_name),
+                            ("if" | "unless", "elsif" | "else")
+                                | ("for", "else")
+                                | ("case", "when" | "else")
+                        )
+                    } else {
+                        matches!(
+                            (tag_name, next_tag_name),
+                            ("if" | "for", "elif" | "elseif" | "else") | ("match", "when" | "else")
+                        )
+                    };
+                    if is_branch {
                         body.push(JinjaTagOrChildren::Tag(next_tag));
                     } else if let Some(JinjaTagOrChildren::Children(nodes)) = body.last_mut() {
                         nodes.push(
@@ -1349,6 +1838,44 @@ impl<'s> Parser<'s> {
         }
     }
 
+    /// Parse body of Liquid tag whose content isn't Liquid or HTML,
+    /// such as `{% raw %}` and `{% schema %}`, and its end tag.
+    fn parse_liquid_raw_block_rest<T>(
+        &mut self,
+        first_tag: JinjaTag<'s>,
+        tag_name: &'s str,
+    ) -> PResult<JinjaBlock<'s, T>>
+    where
+        T: HasJinjaFlowControl<'s>,
+    {
+        let start = self
+            .chars
+            .peek()
+            .map(|(i, _)| *i)
+            .unwrap_or(self.source.len());
+        let Some(end) = find_jinja_end_tag(&self.source[start..], tag_name).map(|i| start + i)
+        else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectJinjaBlockEnd));
+        };
+        while self.chars.next_if(|(i, _)| *i < end).is_some() {}
+
+        let raw = unsafe { self.source.get_unchecked(start..end) };
+        let mut body = vec![JinjaTagOrChildren::Tag(first_tag)];
+        if !raw.is_empty() {
+            let text = TextNode {
+                raw,
+                line_breaks: raw.chars().filter(|c| *c == '\n').count(),
+                start,
+            };
+            let Some(child) = T::from_text(text) else {
+                return Err(self.emit_error(SyntaxErrorKind::ExpectJinjaBlockEnd));
+            };
+            body.push(JinjaTagOrChildren::Children(vec![child]));
+        }
+        body.push(JinjaTagOrChildren::Tag(self.parse_jinja_tag()?));
+        Ok(JinjaBlock { body })
+    }
+
     fn parse_mustache_interpolation(&mut self) -> PResult<(&'s str, usize)> {
         let Some((start, _)) = self
             .chars
@@ -1413,10 +1940,58 @@ impl<'s> Parser<'s> {
                     Some((_, c)) if is_tag_name_char(c) => {
                         self.parse_element().map(NodeKind::Element)
                     }
+                    Some((_, '%'))
+                        if matches!(
+                            self.language,
+                            Language::Erb | Language::Eex | Language::Heex
+                        ) =>
+                    {
+                        match chars.next() {
+                            Some((_, '#' | '!')) => {
+                                self.parse_erb_comment().map(NodeKind::ErbComment)
+                            }
+                            // `<%%` is an escaped literal `<%`
+                            Some((_, '%')) => self.parse_text_node().map(NodeKind::Text),
+                            _ => self.parse_erb_tag_or_block(None),
+                        }
+                    }
+                    Some((_, '!'))
+                        if matches!(self.language, Language::Xml)
+                            && self.chars.peek().is_some_and(|(i, _)| {
+                                self.source[*i..].starts_with("<![CDATA[")
+                            }) =>
+                    {
+                        self.parse_cdata().map(NodeKind::Cdata)
+                    }
+                    Some((_, '?')) if matches!(self.language, Language::Xml) => self
+                        .parse_processing_instruction()
+                        .map(NodeKind::ProcessingInstruction),
+                    Some((_, '?'))
+                        if matches!(self.language, Language::Php)
+                            && self.chars.peek().is_some_and(|(i, _)| {
+                                helpers::is_php_tag_start(&self.source[*i..])
+                            }) =>
+                    {
+                        self.parse_php_tag_or_block(None)
+                    }
                     Some((_, '!')) => {
                         if matches!(
                             self.language,
-                            Language::Html | Language::Astro | Language::Jinja | Language::Vento
+                            Language::Html
+                                | Language::Astro
+                                | Language::Jinja
+                                | Language::Vento
+                                | Language::Handlebars
+                                | Language::Liquid
+                                | Language::GoTemplate
+                                | Language::Erb
+                                | Language::Eex
+                                | Language::Heex
+                                | Language::Blade
+                                | Language::Php
+                                | Language::Razor
+                                | Language::Xml
+                                | Language::Mjml
                         ) {
                             self.try_parse(Parser::parse_comment)
                                 .map(NodeKind::Comment)
@@ -1441,7 +2016,7 @@ impl<'s> Parser<'s> {
                     Some((_, '{'))
                         if matches!(
                             self.language,
-                            Language::Vue | Language::Jinja | Language::Angular
+                            Language::Vue | Language::Jinja | Language::Angular | Language::Liquid
                         ) =>
                     {
                         self.parse_mustache_interpolation().map(|(expr, start)| {
@@ -1449,7 +2024,7 @@ impl<'s> Parser<'s> {
                                 Language::Vue => {
                                     NodeKind::VueInterpolation(VueInterpolation { expr, start })
                                 }
-                                Language::Jinja => {
+                                Language::Jinja | Language::Liquid => {
                                     NodeKind::JinjaInterpolation(JinjaInterpolation { expr })
                                 }
                                 Language::Angular => {
@@ -1465,6 +2040,21 @@ impl<'s> Parser<'s> {
                     Some((_, '{')) if matches!(self.language, Language::Vento) => {
                         self.parse_vento_tag_or_block(None)
                     }
+                    Some((_, '{')) if matches!(self.language, Language::Handlebars) => {
+                        self.parse_handlebars_tag_or_block(None)
+                    }
+                    Some((_, '{')) if matches!(self.language, Language::GoTemplate) => {
+                        self.parse_go_template_tag_or_block(None)
+                    }
+                    Some((_, '{' | '!'))
+                        if matches!(self.language, Language::Blade)
+                            && self.chars.peek().is_some_and(|(i, _)| {
+                                self.source[*i..].starts_with("{{")
+                                    || self.source[*i..].starts_with("{!!")
+                            }) =>
+                    {
+                        self.parse_blade_echo_or_comment()
+                    }
                     Some((_, '#')) if matches!(self.language, Language::Svelte) => {
                         match chars.next() {
                             Some((_, 'i')) => {
@@ -1488,8 +2078,12 @@ impl<'s> Parser<'s> {
                     Some((_, '#')) if matches!(self.language, Language::Jinja) => {
                         self.parse_jinja_comment().map(NodeKind::JinjaComment)
                     }
-                    Some((_, '@')) => self.parse_svelte_at_tag().map(NodeKind::SvelteAtTag),
-                    Some((_, '%')) if matches!(self.language, Language::Jinja) => {
+                    Some((_, '@')) if matches!(self.language, Language::Svelte) => {
+                        self.parse_svelte_at_tag().map(NodeKind::SvelteAtTag)
+                    }
+                    Some((_, '%'))
+                        if matches!(self.language, Language::Jinja | Language::Liquid) =>
+                    {
                         self.parse_jinja_tag_or_block(None, &mut Parser::parse_node)
                     }
                     _ => match self.language {
@@ -1502,10 +2096,7 @@ impl<'s> Parser<'s> {
                 }
             }
             Some((_, '-'))
-                if matches!(
-                    self.language,
-                    Language::Astro | Language::Jinja | Language::Vento
-                ) && !self.state.has_front_matter =>
+                if matches!(self.language, Language::Astro) && !self.state.has_front_matter =>
             {
                 let mut chars = self.chars.clone();
                 chars.next();
@@ -1515,6 +2106,34 @@ impl<'s> Parser<'s> {
                     self.parse_text_node().map(NodeKind::Text)
                 }
             }
+            Some((i, c @ ('-' | '+')))
+                if (matches!(
+                    self.language,
+                    Language::Jinja | Language::Vento | Language::Liquid
+                ) || matches!(
+                    self.language,
+                    Language::Html | Language::Vue | Language::Svelte
+                ) && self.options.front_matter)
+                    && !self.state.has_front_matter
+                    && self.source[..*i].trim().is_empty()
+                    && self.source[*i..].starts_with(if *c == '+' { "+++" } else { "---" }) =>
+            {
+                self.try_parse(Parser::parse_front_matter)
+                    .map(NodeKind::FrontMatter)
+                    .or_else(|_| self.parse_text_node().map(NodeKind::Text))
+            }
+            Some((i, '@'))
+                if matches!(self.language, Language::Blade)
+                    && parse_blade_directive_name_at(self.source, *i).is_some() =>
+            {
+                self.parse_blade_directive_or_block(None)
+            }
+            Some((i, '@'))
+                if matches!(self.language, Language::Razor)
+                    && is_razor_transition(self.source, *i) =>
+            {
+                self.parse_razor_node()
+            }
             Some((_, '@')) if matches!(self.language, Language::Angular) => {
                 let mut chars = self.chars.clone();
                 chars.next();
@@ -1531,14 +2150,14 @@ impl<'s> Parser<'s> {
         }
     }
 
-    fn parse_raw_text_node(&mut self, tag_name: &str) -> PResult<TextNode<'s>> {
+    fn parse_raw_text_node(&mut self, tag_name: &str, allow_nested: bool) -> PResult<TextNode<'s>> {
         let start = self
             .chars
             .peek()
             .map(|(i, _)| *i)
             .unwrap_or(self.source.len());
 
-        let allow_nested = tag_name.eq_ignore_ascii_case("pre");
+        let allow_nested = allow_nested || tag_name.eq_ignore_ascii_case("pre");
         let mut nested = 0u16;
         let mut line_breaks = 0;
         let end;
@@ -1615,9 +2234,33 @@ impl<'s> Parser<'s> {
         let name = self.parse_identifier()?;
         self.skip_ws();
         let expr = self.parse_svelte_or_astro_expr()?;
+        if name == "render" && !expr.0.trim_end().ends_with(')') {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectSvelteRenderCall));
+        }
         Ok(SvelteAtTag { name, expr })
     }
 
+    fn parse_svelte_attachment(&mut self) -> PResult<SvelteAttachment<'s>> {
+        if self
+            .chars
+            .next_if(|(_, c)| *c == '{')
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == '@'))
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == 'a'))
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == 't'))
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == 't'))
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == 'a'))
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == 'c'))
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == 'h'))
+            .and_then(|_| self.chars.next_if(|(_, c)| c.is_ascii_whitespace()))
+            .is_none()
+        {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectSvelteAttachment));
+        };
+        self.skip_ws();
+        self.parse_svelte_or_astro_expr()
+            .map(|expr| SvelteAttachment { expr })
+    }
+
     fn parse_svelte_attr(&mut self) -> PResult<SvelteAttribute<'s>> {
         let name = if self.chars.next_if(|(_, c)| *c == '{').is_some() {
             None
@@ -2233,6 +2876,8 @@ impl<'s> Parser<'s> {
                     | Language::Jinja
                     | Language::Vento
                     | Language::Angular
+                    | Language::Handlebars
+                    | Language::GoTemplate
             ) {
                 *c != '{'
             } else {
@@ -2244,7 +2889,14 @@ impl<'s> Parser<'s> {
 
         if matches!(
             self.language,
-            Language::Vue | Language::Jinja | Language::Vento | Language::Angular
+            Language::Vue
+                | Language::Jinja
+                | Language::Vento
+                | Language::Angular
+                | Language::Handlebars
+                | Language::Liquid
+                | Language::GoTemplate
+                | Language::Blade
         ) && first_char == '{'
             && matches!(self.chars.peek(), Some((_, '{')))
         {
@@ -2256,10 +2908,21 @@ impl<'s> Parser<'s> {
         loop {
             match self.chars.peek() {
                 Some((i, '{')) => match self.language {
-                    Language::Html => {
+                    Language::Html
+                    | Language::Erb
+                    | Language::Eex
+                    | Language::Heex
+                    | Language::Php
+                    | Language::Razor
+                    | Language::Xml
+                    | Language::Mjml => {
                         self.chars.next();
                     }
-                    Language::Vue | Language::Vento | Language::Angular => {
+                    Language::Vue
+                    | Language::Vento
+                    | Language::Angular
+                    | Language::Handlebars
+                    | Language::GoTemplate => {
                         let i = *i;
                         let mut chars = self.chars.clone();
                         chars.next();
@@ -2286,60 +2949,995 @@ impl<'s> Parser<'s> {
                         }
                         self.chars.next();
                     }
-                },
-                Some((i, '<')) => {
-                    let i = *i;
-                    let mut chars = self.chars.clone();
-                    chars.next();
-                    match chars.next() {
-                        Some((_, c))
-                            if is_tag_name_char(c)
-                                || c == '/'
-                                || c == '!'
-                                || c == '>' && matches!(self.language, Language::Astro) =>
-                        {
-                            end = i;
-                            break;
+                    Language::Liquid => {
+                        let i = *i;
+                        let mut chars = self.chars.clone();
+                        chars.next();
+                        if chars.next_if(|(_, c)| *c == '%' || *c == '{').is_some() {
+                            end = i;
+                            break;
+                        }
+                        self.chars.next();
+                    }
+                    Language::Blade => {
+                        let i = *i;
+                        let rest = &self.source[i..];
+                        // `@{{ }}` is escaped echo which is output as is
+                        if (rest.starts_with("{{") || rest.starts_with("{!!"))
+                            && !self.source[..i].ends_with('@')
+                        {
+                            end = i;
+                            break;
+                        }
+                        self.chars.next();
+                    }
+                },
+                Some((i, '<')) => {
+                    let i = *i;
+                    let mut chars = self.chars.clone();
+                    chars.next();
+                    match chars.next() {
+                        Some((_, c))
+                            if is_tag_name_char(c)
+                                || c == '/'
+                                || c == '!'
+                                || c == '>' && matches!(self.language, Language::Astro)
+                                || c == '%'
+                                    && matches!(
+                                        self.language,
+                                        Language::Erb | Language::Eex | Language::Heex
+                                    )
+                                || c == '?'
+                                    && matches!(self.language, Language::Php)
+                                    && helpers::is_php_tag_start(&self.source[i..])
+                                || c == '?' && matches!(self.language, Language::Xml) =>
+                        {
+                            end = i;
+                            break;
                         }
                         _ => {
                             self.chars.next();
                         }
                     }
                 }
-                Some((i, '-'))
-                    if matches!(self.language, Language::Astro) && !self.state.has_front_matter =>
-                {
-                    let i = *i;
+                Some((i, '-'))
+                    if matches!(self.language, Language::Astro) && !self.state.has_front_matter =>
+                {
+                    let i = *i;
+                    let mut chars = self.chars.clone();
+                    chars.next();
+                    if let Some(((_, '-'), (_, '-'))) = chars.next().zip(chars.next()) {
+                        end = i;
+                        break;
+                    }
+                    self.chars.next();
+                }
+                Some((i, '@')) if matches!(self.language, Language::Blade) => {
+                    let i = *i;
+                    if self.source[i..].starts_with("@@") {
+                        self.chars.next();
+                        self.chars.next();
+                    } else if parse_blade_directive_name_at(self.source, i).is_some() {
+                        end = i;
+                        break;
+                    } else {
+                        self.chars.next();
+                    }
+                }
+                Some((i, '@'))
+                    if matches!(self.language, Language::Razor)
+                        && is_razor_transition(self.source, *i) =>
+                {
+                    end = *i;
+                    break;
+                }
+                Some((i, '}' | '@')) if matches!(self.language, Language::Angular) => {
+                    end = *i;
+                    break;
+                }
+                Some((_, c)) => {
+                    if *c == '\n' {
+                        line_breaks += 1;
+                    }
+                    self.chars.next();
+                }
+                None => {
+                    end = self.source.len();
+                    break;
+                }
+            }
+        }
+
+        Ok(TextNode {
+            raw: unsafe { self.source.get_unchecked(start..end) },
+            line_breaks,
+            start,
+        })
+    }
+
+    fn parse_erb_block_children(&mut self) -> PResult<Vec<Node<'s>>> {
+        let mut children = vec![];
+        loop {
+            match self.chars.peek() {
+                Some((_, '<')) => {
+                    let mut chars = self.chars.clone();
+                    chars.next();
+                    if chars.next_if(|(_, c)| *c == '%').is_some()
+                        && chars
+                            .next_if(|(_, c)| matches!(c, '#' | '!' | '%'))
+                            .is_none()
+                    {
+                        break;
+                    }
+                    children.push(self.parse_node()?);
+                }
+                Some(..) => {
+                    children.push(self.parse_node()?);
+                }
+                None => return Err(self.emit_error(SyntaxErrorKind::ExpectErbBlockEnd)),
+            }
+        }
+        Ok(children)
+    }
+
+    /// Parse `<%# comment %>` or HEEx `<%!-- comment --%>`.
+    fn parse_erb_comment(&mut self) -> PResult<ErbComment<'s>> {
+        let Some((start, _)) = self
+            .chars
+            .next_if(|(_, c)| *c == '<')
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == '%'))
+        else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectErbComment));
+        };
+        let (long, start, closing) = if self.chars.next_if(|(_, c)| *c == '#').is_some() {
+            (false, start + 2, "%>")
+        } else if self
+            .chars
+            .next_if(|(_, c)| *c == '!')
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == '-'))
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == '-'))
+            .is_some()
+        {
+            (true, start + 4, "--%>")
+        } else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectErbComment));
+        };
+
+        let Some(end) = self
+            .source
+            .get(start..)
+            .and_then(|rest| rest.find(closing))
+            .map(|index| start + index)
+        else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectErbComment));
+        };
+        while self
+            .chars
+            .next_if(|(i, _)| *i < end + closing.len())
+            .is_some()
+        {}
+
+        Ok(ErbComment {
+            raw: unsafe { self.source.get_unchecked(start..end) },
+            long,
+        })
+    }
+
+    fn parse_erb_tag(&mut self) -> PResult<ErbTag<'s>> {
+        let Some((start, _)) = self
+            .chars
+            .next_if(|(_, c)| *c == '<')
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == '%'))
+        else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectErbTag));
+        };
+        let prefix_start = start + 1;
+        let mut code_start = prefix_start;
+        while let Some((_, c)) = self.chars.next_if(|(_, c)| matches!(c, '=' | '-')) {
+            code_start += c.len_utf8();
+        }
+
+        let Some(end) = self
+            .source
+            .get(code_start..)
+            .and_then(|rest| rest.find("%>"))
+            .map(|index| code_start + index)
+        else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectErbTag));
+        };
+        while self.chars.next_if(|(i, _)| *i < end + "%>".len()).is_some() {}
+
+        let code = unsafe { self.source.get_unchecked(code_start..end) };
+        let (code, trim_next) = code
+            .strip_suffix('-')
+            .map(|code| (code, true))
+            .unwrap_or((code, false));
+        Ok(ErbTag {
+            prefix: unsafe { self.source.get_unchecked(prefix_start..code_start) },
+            code,
+            start: code_start,
+            trim_next,
+        })
+    }
+
+    fn parse_erb_tag_or_block(&mut self, first_tag: Option<ErbTag<'s>>) -> PResult<NodeKind<'s>> {
+        let first_tag = if let Some(first_tag) = first_tag {
+            first_tag
+        } else {
+            self.parse_erb_tag()?
+        };
+
+        if let helpers::TemplateTagKind::Open =
+            helpers::get_erb_tag_kind(first_tag.code, &self.language)
+        {
+            let mut body = vec![ErbTagOrChildren::Tag(first_tag)];
+
+            loop {
+                let mut children = self.parse_erb_block_children()?;
+                if !children.is_empty() {
+                    if let Some(ErbTagOrChildren::Children(nodes)) = body.last_mut() {
+                        nodes.append(&mut children);
+                    } else {
+                        body.push(ErbTagOrChildren::Children(children));
+                    }
+                }
+                let next_tag = self.parse_erb_tag()?;
+                match helpers::get_erb_tag_kind(next_tag.code, &self.language) {
+                    helpers::TemplateTagKind::Close => {
+                        body.push(ErbTagOrChildren::Tag(next_tag));
+                        break;
+                    }
+                    helpers::TemplateTagKind::Middle => {
+                        body.push(ErbTagOrChildren::Tag(next_tag));
+                    }
+                    _ => {
+                        let node = self
+                            .with_taken(|parser| parser.parse_erb_tag_or_block(Some(next_tag)))
+                            .map(|(kind, raw)| Node { kind, raw })?;
+                        if let Some(ErbTagOrChildren::Children(nodes)) = body.last_mut() {
+                            nodes.push(node);
+                        } else {
+                            body.push(ErbTagOrChildren::Children(vec![node]));
+                        }
+                    }
+                }
+            }
+            Ok(NodeKind::ErbBlock(ErbBlock { body }))
+        } else {
+            Ok(NodeKind::ErbTag(first_tag))
+        }
+    }
+
+    /// Parse XML processing instruction or declaration like `<?xml version="1.0"?>`.
+    fn parse_processing_instruction(&mut self) -> PResult<ProcessingInstruction<'s>> {
+        let Some((start, _)) = self
+            .chars
+            .next_if(|(_, c)| *c == '<')
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == '?'))
+        else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectProcessingInstruction));
+        };
+        let start = start + 1;
+        let Some(end) = self.source[start..].find("?>").map(|i| start + i) else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectProcessingInstruction));
+        };
+        while self.chars.next_if(|(i, _)| *i < end + "?>".len()).is_some() {}
+        Ok(ProcessingInstruction {
+            raw: unsafe { self.source.get_unchecked(start..end) },
+        })
+    }
+
+    fn parse_php_block_children(&mut self) -> PResult<Vec<Node<'s>>> {
+        let mut children = vec![];
+        loop {
+            match self.chars.peek() {
+                Some((i, '<')) if helpers::is_php_tag_start(&self.source[*i..]) => break,
+                Some(..) => {
+                    children.push(self.parse_node()?);
+                }
+                None => return Err(self.emit_error(SyntaxErrorKind::ExpectPhpBlockEnd)),
+            }
+        }
+        Ok(children)
+    }
+
+    /// Parse `<?php code ?>` or `<?= expr ?>`.
+    /// The closing `?>` can be omitted at the end of file.
+    fn parse_php_tag(&mut self) -> PResult<PhpTag<'s>> {
+        let Some(start) = self
+            .chars
+            .peek()
+            .map(|(i, _)| *i)
+            .filter(|i| helpers::is_php_tag_start(&self.source[*i..]))
+        else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectPhpTag));
+        };
+        let echo = self.source[start..].starts_with("<?=");
+        let code_start = start + if echo { "<?=".len() } else { "<?php".len() };
+
+        let (end, closed) = find_php_tag_end(&self.source[code_start..])
+            .map_or((self.source.len(), false), |index| {
+                (code_start + index, true)
+            });
+        let next = if closed { end + "?>".len() } else { end };
+        while self.chars.next_if(|(i, _)| *i < next).is_some() {}
+
+        Ok(PhpTag {
+            code: unsafe { self.source.get_unchecked(code_start..end) },
+            start: code_start,
+            echo,
+            closed,
+        })
+    }
+
+    fn parse_php_tag_or_block(&mut self, first_tag: Option<PhpTag<'s>>) -> PResult<NodeKind<'s>> {
+        let first_tag = if let Some(first_tag) = first_tag {
+            first_tag
+        } else {
+            self.parse_php_tag()?
+        };
+
+        if first_tag.echo {
+            return Ok(NodeKind::PhpTag(first_tag));
+        }
+        if let helpers::TemplateTagKind::Open = helpers::get_php_tag_kind(first_tag.code) {
+            let mut body = vec![PhpTagOrChildren::Tag(first_tag)];
+
+            loop {
+                let mut children = self.parse_php_block_children()?;
+                if !children.is_empty() {
+                    if let Some(PhpTagOrChildren::Children(nodes)) = body.last_mut() {
+                        nodes.append(&mut children);
+                    } else {
+                        body.push(PhpTagOrChildren::Children(children));
+                    }
+                }
+                let next_tag = self.parse_php_tag()?;
+                match helpers::get_php_tag_kind(next_tag.code) {
+                    helpers::TemplateTagKind::Close if !next_tag.echo => {
+                        body.push(PhpTagOrChildren::Tag(next_tag));
+                        break;
+                    }
+                    helpers::TemplateTagKind::Middle if !next_tag.echo => {
+                        body.push(PhpTagOrChildren::Tag(next_tag));
+                    }
+                    _ => {
+                        let node = self
+                            .with_taken(|parser| parser.parse_php_tag_or_block(Some(next_tag)))
+                            .map(|(kind, raw)| Node { kind, raw })?;
+                        if let Some(PhpTagOrChildren::Children(nodes)) = body.last_mut() {
+                            nodes.push(node);
+                        } else {
+                            body.push(PhpTagOrChildren::Children(vec![node]));
+                        }
+                    }
+                }
+            }
+            Ok(NodeKind::PhpBlock(PhpBlock { body }))
+        } else {
+            Ok(NodeKind::PhpTag(first_tag))
+        }
+    }
+
+    /// Parse Razor syntax starting with `@`, such as `@if`, `@{ }`, `@model` or `@expr`.
+    fn parse_razor_node(&mut self) -> PResult<NodeKind<'s>> {
+        let Some(start) = self
+            .chars
+            .peek()
+            .and_then(|(i, c)| if *c == '@' { Some(*i) } else { None })
+        else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectRazorExpr));
+        };
+        let rest = &self.source[start + 1..];
+        if rest.starts_with('*') {
+            return self.parse_razor_comment().map(NodeKind::RazorComment);
+        }
+        if rest.starts_with('{') {
+            self.chars.next();
+            self.chars.next();
+            return self
+                .parse_razor_code_children()
+                .map(|children| NodeKind::RazorCodeBlock(RazorCodeBlock { children }));
+        }
+        if is_razor_control_flow(rest, true) {
+            return self
+                .parse_razor_control_flow()
+                .map(NodeKind::RazorControlFlow);
+        }
+
+        let name = rest
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap_or_default();
+        let name_end = start + 1 + name.len();
+        match name {
+            "code" | "functions" => {
+                let body_start = self.source[name_end..]
+                    .strip_prefix(|c: char| c.is_ascii_whitespace())
+                    .map_or(&self.source[name_end..], str::trim_start)
+                    .strip_prefix('{')
+                    .map(|body| self.source.len() - body.len());
+                let Some((body_start, body_end)) = body_start.and_then(|body_start| {
+                    scan_csharp(&self.source[body_start..], &['}'])
+                        .filter(|i| self.source[body_start + i..].starts_with('}'))
+                        .map(|i| (body_start, body_start + i))
+                }) else {
+                    return Err(self.emit_error(SyntaxErrorKind::ExpectRazorBlockEnd));
+                };
+                while self.chars.next_if(|(i, _)| *i <= body_end).is_some() {}
+                Ok(NodeKind::RazorDirective(RazorDirective {
+                    name,
+                    value: "",
+                    body: Some((
+                        unsafe { self.source.get_unchecked(body_start..body_end) },
+                        body_start,
+                    )),
+                }))
+            }
+            "addTagHelper" | "attribute" | "implements" | "inherits" | "inject" | "layout"
+            | "model" | "namespace" | "page" | "preservewhitespace" | "removeTagHelper"
+            | "rendermode" | "tagHelperPrefix" | "typeparam" | "using" => {
+                let line_end = self.source[name_end..]
+                    .find('\n')
+                    .map_or(self.source.len(), |i| name_end + i);
+                while self.chars.next_if(|(i, _)| *i < line_end).is_some() {}
+                Ok(NodeKind::RazorDirective(RazorDirective {
+                    name,
+                    value: unsafe { self.source.get_unchecked(name_end..line_end) }.trim(),
+                    body: None,
+                }))
+            }
+            _ => {
+                let len = razor_expr_len(rest);
+                if len == 0 {
+                    return Err(self.emit_error(SyntaxErrorKind::ExpectRazorExpr));
+                }
+                let end = start + 1 + len;
+                while self.chars.next_if(|(i, _)| *i < end).is_some() {}
+                Ok(NodeKind::RazorExpr(
+                    if let Some(expr) = rest[..len]
+                        .strip_prefix('(')
+                        .and_then(|expr| expr.strip_suffix(')'))
+                    {
+                        RazorExpr {
+                            expr,
+                            explicit: true,
+                        }
+                    } else {
+                        RazorExpr {
+                            expr: &rest[..len],
+                            explicit: false,
+                        }
+                    },
+                ))
+            }
+        }
+    }
+
+    fn parse_razor_comment(&mut self) -> PResult<RazorComment<'s>> {
+        let Some((start, _)) = self
+            .chars
+            .next_if(|(_, c)| *c == '@')
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == '*'))
+        else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectRazorComment));
+        };
+        let start = start + 1;
+        let Some(end) = self.source[start..].find("*@").map(|i| start + i) else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectRazorComment));
+        };
+        while self.chars.next_if(|(i, _)| *i < end + "*@".len()).is_some() {}
+        Ok(RazorComment {
+            raw: unsafe { self.source.get_unchecked(start..end) },
+        })
+    }
+
+    /// Parse children of Razor code block until `}`, after `{` has been consumed.
+    /// Markup and Razor expressions are parsed as nodes, while other C# code is kept as statements.
+    fn parse_razor_code_children(&mut self) -> PResult<Vec<Node<'s>>> {
+        let mut children = vec![];
+        loop {
+            match self.chars.peek() {
+                Some((_, '}')) => {
+                    self.chars.next();
+                    break;
+                }
+                Some((start, c)) if c.is_ascii_whitespace() => {
+                    let start = *start;
+                    let mut end = start;
+                    let mut line_breaks = 0;
+                    while let Some((i, c)) = self.chars.next_if(|(_, c)| c.is_ascii_whitespace()) {
+                        if c == '\n' {
+                            line_breaks += 1;
+                        }
+                        end = i + 1;
+                    }
+                    let raw = unsafe { self.source.get_unchecked(start..end) };
+                    children.push(Node {
+                        kind: NodeKind::Text(TextNode {
+                            raw,
+                            line_breaks,
+                            start,
+                        }),
+                        raw,
+                    });
+                }
+                Some((i, '<'))
+                    if self.source[i + 1..]
+                        .starts_with(|c: char| is_tag_name_char(c) || c == '!') =>
+                {
+                    children.push(self.parse_node()?);
+                }
+                Some((i, '@')) if !self.source[i + 1..].starts_with(':') => {
+                    let (kind, raw) = self.with_taken(Parser::parse_razor_node)?;
+                    children.push(Node { kind, raw });
+                }
+                // bare block like `{ }` after `case` label
+                Some((_, '{')) => {
+                    let (kind, raw) = self.with_taken(|parser| {
+                        parser
+                            .parse_razor_control_flow()
+                            .map(NodeKind::RazorControlFlow)
+                    })?;
+                    children.push(Node { kind, raw });
+                }
+                Some((i, _)) if is_razor_control_flow(&self.source[*i..], false) => {
+                    let (kind, raw) = self.with_taken(|parser| {
+                        parser
+                            .try_parse(Parser::parse_razor_control_flow)
+                            .map(NodeKind::RazorControlFlow)
+                            .or_else(|_| {
+                                // such as `if (a) return;` without braces
+                                parser.parse_razor_statement().map(NodeKind::RazorStatement)
+                            })
+                    })?;
+                    children.push(Node { kind, raw });
+                }
+                Some(..) => {
+                    let (kind, raw) = self.with_taken(|parser| {
+                        parser.parse_razor_statement().map(NodeKind::RazorStatement)
+                    })?;
+                    children.push(Node { kind, raw });
+                }
+                None => return Err(self.emit_error(SyntaxErrorKind::ExpectRazorBlockEnd)),
+            }
+        }
+        Ok(children)
+    }
+
+    fn parse_razor_control_flow(&mut self) -> PResult<RazorControlFlow<'s>> {
+        let at_sign = self.chars.next_if(|(_, c)| *c == '@').is_some();
+        let mut branches: Vec<RazorControlFlowBranch> = vec![];
+        let mut trailer = None;
+        loop {
+            let start = self
+                .chars
+                .peek()
+                .map(|(i, _)| *i)
+                .unwrap_or(self.source.len());
+            let Some(header_end) = scan_csharp(&self.source[start..], &['{', ';'])
+                .map(|i| start + i)
+                .filter(|i| self.source[*i..].starts_with('{'))
+            else {
+                return Err(self.emit_error(SyntaxErrorKind::ExpectRazorBlock));
+            };
+            while self.chars.next_if(|(i, _)| *i <= header_end).is_some() {}
+            let header = unsafe { self.source.get_unchecked(start..header_end) }.trim_end();
+            let children = self.parse_razor_code_children()?;
+            branches.push(RazorControlFlowBranch { header, children });
+
+            let Some(next) = self.chars.peek().map(|(i, _)| *i) else {
+                break;
+            };
+            let rest = &self.source[next..];
+            let trimmed = rest.trim_start();
+            let ws_end = next + rest.len() - trimmed.len();
+            let keyword = branches[0]
+                .header
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .next()
+                .unwrap_or_default();
+            let next_keyword = trimmed
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .next()
+                .unwrap_or_default();
+            match (keyword, next_keyword) {
+                ("if", "else") | ("try", "catch" | "finally") => {
+                    while self.chars.next_if(|(i, _)| *i < ws_end).is_some() {}
+                }
+                ("do", "while") => {
+                    if let Some(end) =
+                        scan_csharp(trimmed, &[';']).filter(|i| trimmed[*i..].starts_with(';'))
+                    {
+                        trailer = Some(&trimmed[..=end]);
+                        while self.chars.next_if(|(i, _)| *i <= ws_end + end).is_some() {}
+                    }
+                    break;
+                }
+                _ => break,
+            }
+        }
+        Ok(RazorControlFlow {
+            at_sign,
+            branches,
+            trailer,
+        })
+    }
+
+    /// Parse C# statement or `@:` text line inside Razor code block.
+    fn parse_razor_statement(&mut self) -> PResult<RazorStatement<'s>> {
+        let start = self
+            .chars
+            .peek()
+            .map(|(i, _)| *i)
+            .unwrap_or(self.source.len());
+        let rest = &self.source[start..];
+        let end = if rest.starts_with("@:") {
+            rest.find('\n').unwrap_or(rest.len())
+        } else {
+            let first_word = rest
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .next()
+                .unwrap_or_default();
+            let stop = if matches!(first_word, "case" | "default") {
+                ':'
+            } else {
+                ';'
+            };
+            match scan_csharp(rest, &[stop]) {
+                Some(i) if rest[i..].starts_with(stop) => i + 1,
+                // unmatched closing bracket, such as `}` of block
+                Some(i) => i,
+                None => rest.len(),
+            }
+        };
+        let code = rest[..end].trim_end();
+        if code.is_empty() {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectRazorBlockEnd));
+        }
+        while self
+            .chars
+            .next_if(|(i, _)| *i < start + code.len())
+            .is_some()
+        {}
+        Ok(RazorStatement { code, start })
+    }
+
+    fn parse_go_template_block_children(&mut self) -> PResult<Vec<Node<'s>>> {
+        let mut children = vec![];
+        loop {
+            match self.chars.peek() {
+                Some((_, '{')) => {
+                    let mut chars = self.chars.clone();
+                    chars.next();
+                    if chars.next_if(|(_, c)| *c == '{').is_some() {
+                        break;
+                    }
+                    children.push(self.parse_node()?);
+                }
+                Some(..) => {
+                    children.push(self.parse_node()?);
+                }
+                None => return Err(self.emit_error(SyntaxErrorKind::ExpectGoTemplateBlockEnd)),
+            }
+        }
+        Ok(children)
+    }
+
+    fn parse_go_template_tag_or_block(
+        &mut self,
+        first_tag: Option<&'s str>,
+    ) -> PResult<NodeKind<'s>> {
+        let first_tag = if let Some(first_tag) = first_tag {
+            first_tag
+        } else {
+            self.parse_mustache_interpolation()?.0
+        };
+        let (tag, trim_prev, trim_next) = helpers::strip_go_template_trim_markers(first_tag);
+        let trimmed = tag.trim();
+
+        if let Some(raw) = trimmed
+            .strip_prefix("/*")
+            .and_then(|rest| rest.strip_suffix("*/"))
+        {
+            return Ok(NodeKind::GoTemplateComment(GoTemplateComment {
+                raw,
+                trim_prev,
+                trim_next,
+            }));
+        }
+
+        if matches!(
+            helpers::parse_vento_tag(trimmed).0,
+            "if" | "range" | "with" | "define" | "block"
+        ) {
+            let mut body = vec![GoTemplateTagOrChildren::Tag(GoTemplateTag {
+                tag,
+                trim_prev,
+                trim_next,
+            })];
+
+            loop {
+                let mut children = self.parse_go_template_block_children()?;
+                if !children.is_empty() {
+                    if let Some(GoTemplateTagOrChildren::Children(nodes)) = body.last_mut() {
+                        nodes.append(&mut children);
+                    } else {
+                        body.push(GoTemplateTagOrChildren::Children(children));
+                    }
+                }
+                let (next_tag, _) = self.parse_mustache_interpolation()?;
+                let (tag, trim_prev, trim_next) = helpers::strip_go_template_trim_markers(next_tag);
+                match helpers::parse_vento_tag(tag).0 {
+                    "end" => {
+                        body.push(GoTemplateTagOrChildren::Tag(GoTemplateTag {
+                            tag,
+                            trim_prev,
+                            trim_next,
+                        }));
+                        break;
+                    }
+                    "else" => {
+                        body.push(GoTemplateTagOrChildren::Tag(GoTemplateTag {
+                            tag,
+                            trim_prev,
+                            trim_next,
+                        }));
+                    }
+                    _ => {
+                        let node = self
+                            .with_taken(|parser| {
+                                parser.parse_go_template_tag_or_block(Some(next_tag))
+                            })
+                            .map(|(kind, raw)| Node { kind, raw })?;
+                        if let Some(GoTemplateTagOrChildren::Children(nodes)) = body.last_mut() {
+                            nodes.push(node);
+                        } else {
+                            body.push(GoTemplateTagOrChildren::Children(vec![node]));
+                        }
+                    }
+                }
+            }
+            Ok(NodeKind::GoTemplateBlock(GoTemplateBlock { body }))
+        } else {
+            Ok(NodeKind::GoTemplateInterpolation(GoTemplateInterpolation {
+                expr: tag,
+                trim_prev,
+                trim_next,
+            }))
+        }
+    }
+
+    /// Parse HEEx `{expr}` used as attribute value or dynamic attributes like `{@rest}`.
+    fn parse_heex_braced(&mut self) -> PResult<&'s str> {
+        let Some((start, _)) = self.chars.next_if(|(_, c)| *c == '{') else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectChar('{')));
+        };
+        let mut braces_stack = 0usize;
+        let mut quote = None;
+        loop {
+            match self.chars.next() {
+                Some((_, c)) if quote.is_some_and(|q| q == c) => quote = None,
+                Some((_, '\\')) if quote.is_some() => {
+                    self.chars.next();
+                }
+                Some(..) if quote.is_some() => {}
+                Some((_, c @ ('"' | '\''))) => quote = Some(c),
+                Some((_, '{')) => braces_stack += 1,
+                Some((i, '}')) => {
+                    if braces_stack == 0 {
+                        return Ok(unsafe { self.source.get_unchecked(start..=i) });
+                    }
+                    braces_stack -= 1;
+                }
+                Some(..) => {}
+                None => return Err(self.emit_error(SyntaxErrorKind::ExpectChar('}'))),
+            }
+        }
+    }
+
+    fn parse_handlebars_block_children(&mut self) -> PResult<Vec<Node<'s>>> {
+        let mut children = vec![];
+        loop {
+            match self.chars.peek() {
+                Some((_, '{')) => {
                     let mut chars = self.chars.clone();
                     chars.next();
-                    if let Some(((_, '-'), (_, '-'))) = chars.next().zip(chars.next()) {
-                        end = i;
+                    if chars.next_if(|(_, c)| *c == '{').is_some() {
+                        if chars.next_if(|(_, c)| *c == '!').is_some() {
+                            children.push(self.parse_node()?);
+                            continue;
+                        }
                         break;
                     }
-                    self.chars.next();
+                    children.push(self.parse_node()?);
                 }
-                Some((i, '}' | '@')) if matches!(self.language, Language::Angular) => {
-                    end = *i;
-                    break;
+                Some(..) => {
+                    children.push(self.parse_node()?);
                 }
-                Some((_, c)) => {
-                    if *c == '\n' {
-                        line_breaks += 1;
+                None => return Err(self.emit_error(SyntaxErrorKind::ExpectHandlebarsBlockEnd)),
+            }
+        }
+        Ok(children)
+    }
+
+    /// Parse block params of Glimmer component like `as |item index|`,
+    /// which is treated as an attribute without value.
+    fn parse_handlebars_block_params(&mut self) -> PResult<NativeAttribute<'s>> {
+        let Some((start, _)) = self
+            .chars
+            .next_if(|(_, c)| *c == 'a')
+            .and_then(|start| self.chars.next_if(|(_, c)| *c == 's').map(|_| start))
+            .and_then(|start| {
+                self.chars
+                    .next_if(|(_, c)| c.is_ascii_whitespace())
+                    .map(|_| start)
+            })
+        else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectAttrName));
+        };
+        self.skip_ws();
+        if self.chars.next_if(|(_, c)| *c == '|').is_none() {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectChar('|')));
+        }
+        let Some((end, _)) = self.chars.find(|(_, c)| *c == '|') else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectChar('|')));
+        };
+        Ok(NativeAttribute {
+            name: unsafe { self.source.get_unchecked(start..=end) },
+            value: None,
+            quote: None,
+        })
+    }
+
+    fn parse_handlebars_comment(&mut self) -> PResult<HandlebarsComment<'s>> {
+        let Some((start, _)) = self
+            .chars
+            .next_if(|(_, c)| *c == '{')
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == '{'))
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == '!'))
+        else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectHandlebarsComment));
+        };
+        let long = self
+            .chars
+            .next_if(|(_, c)| *c == '-')
+            .and_then(|_| self.chars.next_if(|(_, c)| *c == '-'))
+            .is_some();
+        let start = start + if long { 3 } else { 1 };
+        let closing = if long { "--}}" } else { "}}" };
+
+        let Some(end) = self
+            .source
+            .get(start..)
+            .and_then(|rest| rest.find(closing))
+            .map(|index| start + index)
+        else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectHandlebarsComment));
+        };
+        while self
+            .chars
+            .next_if(|(i, _)| *i < end + closing.len())
+            .is_some()
+        {}
+
+        Ok(HandlebarsComment {
+            raw: unsafe { self.source.get_unchecked(start..end) },
+            long,
+        })
+    }
+
+    fn parse_handlebars_tag_or_block(
+        &mut self,
+        first_tag: Option<&'s str>,
+    ) -> PResult<NodeKind<'s>> {
+        let first_tag = if let Some(first_tag) = first_tag {
+            first_tag
+        } else {
+            let mut chars = self.chars.clone();
+            if let Some(((_, '{'), ((_, '{'), (_, '!')))) =
+                chars.next().zip(chars.next().zip(chars.next()))
+            {
+                return self
+                    .parse_handlebars_comment()
+                    .map(NodeKind::HandlebarsComment);
+            }
+            self.parse_mustache_interpolation()?.0
+        };
+
+        if let Some(expr) = first_tag
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+        {
+            let (expr, trim_prev, trim_next) = helpers::strip_handlebars_trim_markers(expr);
+            return Ok(NodeKind::HandlebarsInterpolation(HandlebarsInterpolation {
+                expr,
+                triple: true,
+                trim_prev,
+                trim_next,
+            }));
+        }
+        let (tag, trim_prev, trim_next) = helpers::strip_handlebars_trim_markers(first_tag);
+        let trimmed = tag.trim_start();
+
+        let block_name = trimmed
+            .strip_prefix('#')
+            .or_else(|| {
+                trimmed
+                    .strip_prefix('^')
+                    .filter(|rest| !rest.trim().is_empty())
+            })
+            .map(helpers::parse_handlebars_block_name);
+
+        if let Some(block_name) = block_name {
+            let mut body = vec![HandlebarsTagOrChildren::Tag(HandlebarsTag {
+                tag,
+                trim_prev,
+                trim_next,
+            })];
+
+            loop {
+                let mut children = self.parse_handlebars_block_children()?;
+                if !children.is_empty() {
+                    if let Some(HandlebarsTagOrChildren::Children(nodes)) = body.last_mut() {
+                        nodes.append(&mut children);
+                    } else {
+                        body.push(HandlebarsTagOrChildren::Children(children));
                     }
-                    self.chars.next();
                 }
-                None => {
-                    end = self.source.len();
+                if let Ok((next_tag, _)) = self.parse_mustache_interpolation() {
+                    let (tag, trim_prev, trim_next) =
+                        helpers::strip_handlebars_trim_markers(next_tag);
+                    let trimmed = tag.trim();
+                    if trimmed.strip_prefix('/').is_some_and(|name| {
+                        helpers::parse_handlebars_block_name(name) == block_name
+                    }) {
+                        body.push(HandlebarsTagOrChildren::Tag(HandlebarsTag {
+                            tag,
+                            trim_prev,
+                            trim_next,
+                        }));
+                        break;
+                    }
+                    if trimmed == "^" || helpers::parse_vento_tag(trimmed).0 == "else" {
+                        body.push(HandlebarsTagOrChildren::Tag(HandlebarsTag {
+                            tag,
+                            trim_prev,
+                            trim_next,
+                        }));
+                    } else {
+                        let node = self
+                            .with_taken(|parser| {
+                                parser.parse_handlebars_tag_or_block(Some(next_tag))
+                            })
+                            .map(|(kind, raw)| Node { kind, raw })?;
+                        if let Some(HandlebarsTagOrChildren::Children(nodes)) = body.last_mut() {
+                            nodes.push(node);
+                        } else {
+                            body.push(HandlebarsTagOrChildren::Children(vec![node]));
+                        }
+                    }
+                } else {
                     break;
                 }
             }
+            Ok(NodeKind::HandlebarsBlock(HandlebarsBlock { body }))
+        } else if trimmed.starts_with(['/', '>', '^'])
+            || helpers::parse_vento_tag(trimmed).0 == "else"
+        {
+            Ok(NodeKind::HandlebarsTag(HandlebarsTag {
+                tag,
+                trim_prev,
+                trim_next,
+            }))
+        } else {
+            Ok(NodeKind::HandlebarsInterpolation(HandlebarsInterpolation {
+                expr: tag,
+                triple: false,
+                trim_prev,
+                trim_next,
+            }))
         }
-
-        Ok(TextNode {
-            raw: unsafe { self.source.get_unchecked(start..end) },
-            line_breaks,
-            start,
-        })
     }
 
     fn parse_vento_block_children(&mut self) -> PResult<Vec<Node<'s>>> {
@@ -2400,12 +3998,29 @@ impl<'s> Parser<'s> {
 
         let (tag_name, tag_rest) = helpers::parse_vento_tag(first_tag);
 
+        if helpers::is_vento_raw_block(first_tag, &self.options.vento_custom_tags) {
+            return self.parse_vento_raw_block_rest(
+                VentoTag {
+                    tag: first_tag,
+                    trim_prev,
+                    trim_next,
+                },
+                tag_name,
+            );
+        }
+
+        let custom_tag = self.options.vento_custom_tags.get(tag_name);
         let is_function = tag_name == "function"
-            || matches!(tag_name, "async" | "export") && tag_rest.starts_with("function");
-        if matches!(tag_name, "for" | "if" | "layout")
-            || matches!(tag_name, "set" | "export") && !first_tag.contains('=')
-            || is_function
-        {
+            || matches!(tag_name, "async" | "export") && tag_rest.starts_with("function")
+            || tag_name == "export" && tag_rest.starts_with("async function");
+        let is_block = if let Some(kind) = custom_tag {
+            matches!(kind, VentoTagKind::Block)
+        } else {
+            matches!(tag_name, "for" | "if" | "layout")
+                || matches!(tag_name, "set" | "export") && !first_tag.contains('=')
+                || is_function
+        };
+        if is_block {
             let mut body = vec![VentoTagOrChildren::Tag(VentoTag {
                 tag: first_tag,
                 trim_prev,
@@ -2475,7 +4090,7 @@ impl<'s> Parser<'s> {
                 }
             }
             Ok(NodeKind::VentoBlock(VentoBlock { body }))
-        } else if is_vento_interpolation(tag_name) {
+        } else if custom_tag.is_none() && helpers::is_vento_interpolation(tag_name) {
             Ok(NodeKind::VentoInterpolation(VentoInterpolation {
                 expr: first_tag,
                 start: first_tag_start,
@@ -2489,6 +4104,54 @@ impl<'s> Parser<'s> {
         }
     }
 
+    fn parse_vento_raw_block_rest(
+        &mut self,
+        first_tag: VentoTag<'s>,
+        tag_name: &str,
+    ) -> PResult<NodeKind<'s>> {
+        let start = self
+            .chars
+            .peek()
+            .map(|(i, _)| *i)
+            .unwrap_or(self.source.len());
+        let Some(end) = find_vento_end_tag(&self.source[start..], tag_name).map(|i| start + i)
+        else {
+            return Err(self.emit_error(SyntaxErrorKind::ExpectVentoBlockEnd));
+        };
+        while self.chars.next_if(|(i, _)| *i < end).is_some() {}
+
+        let raw = unsafe { self.source.get_unchecked(start..end) };
+        let mut body = vec![VentoTagOrChildren::Tag(first_tag)];
+        if !raw.is_empty() {
+            body.push(VentoTagOrChildren::Children(vec![Node {
+                kind: NodeKind::Text(TextNode {
+                    raw,
+                    line_breaks: raw.chars().filter(|c| *c == '\n').count(),
+                    start,
+                }),
+                raw,
+            }]));
+        }
+
+        let (mut last_tag, _) = self.parse_mustache_interpolation()?;
+        let mut trim_prev = false;
+        let mut trim_next = false;
+        if let Some(tag) = last_tag.strip_prefix('-') {
+            last_tag = tag;
+            trim_prev = true;
+        }
+        if let Some(tag) = last_tag.strip_suffix('-') {
+            last_tag = tag;
+            trim_next = true;
+        }
+        body.push(VentoTagOrChildren::Tag(VentoTag {
+            tag: last_tag,
+            trim_prev,
+            trim_next,
+        }));
+        Ok(NodeKind::VentoBlock(VentoBlock { body }))
+    }
+
     fn parse_vue_directive(&mut self) -> PResult<VueDirective<'s>> {
         let name = match self.chars.peek() {
             Some((_, ':')) => {
@@ -2558,26 +4221,356 @@ fn is_attr_name_char(c: char) -> bool {
     !matches!(c, '"' | '\'' | '>' | '/' | '=') && !c.is_ascii_whitespace()
 }
 
-fn parse_jinja_tag_name<'s>(tag: &JinjaTag<'s>) -> &'s str {
-    let trimmed = tag.content.trim_start_matches(['+', '-']).trim_start();
+pub(crate) fn parse_jinja_tag_name<'s>(tag: &JinjaTag<'s>) -> &'s str {
+    let trimmed = tag.content.trim_start_matches(['+', '-', '~']).trim_start();
     trimmed
         .split_once(|c: char| c.is_ascii_whitespace())
         .map(|(name, _)| name)
         .unwrap_or(trimmed)
 }
 
-fn is_vento_interpolation(tag_name: &str) -> bool {
-    !matches!(
-        tag_name,
-        "if" | "else"
-            | "for"
-            | "set"
+/// Find position of end tag like `{% endraw %}` which closes Jinja or Liquid block.
+fn find_jinja_end_tag(source: &str, tag_name: &str) -> Option<usize> {
+    source.match_indices("{%").map(|(i, _)| i).find(|i| {
+        source[i + 2..]
+            .trim_start_matches(['-', '+', '~'])
+            .trim_start()
+            .strip_prefix("end")
+            .and_then(|rest| rest.strip_prefix(tag_name))
+            .is_some_and(|rest| rest.starts_with(|c: char| !is_tag_name_char(c)))
+    })
+}
+
+fn find_vento_end_tag(source: &str, tag_name: &str) -> Option<usize> {
+    source.match_indices("{{").map(|(i, _)| i).find(|i| {
+        source[i + 2..]
+            .trim_start_matches('-')
+            .trim_start()
+            .strip_prefix('/')
+            .and_then(|rest| rest.strip_prefix(tag_name))
+            .is_some_and(|rest| rest.trim_start().starts_with(['-', '}']))
+    })
+}
+
+/// Check if `@` at the given position starts Razor code instead of literal text,
+/// so email addresses and escaped `@@` are excluded.
+fn is_razor_transition(source: &str, i: usize) -> bool {
+    !source[..i].ends_with(|c: char| c.is_alphanumeric() || c == '@')
+        && source[i + 1..]
+            .starts_with(|c: char| matches!(c, '*' | '{' | '(' | '_') || c.is_alphabetic())
+}
+
+/// Check if code (after `@` if any) starts Razor control structure like `if (a) {`.
+fn is_razor_control_flow(code: &str, at_sign: bool) -> bool {
+    let keyword = code
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or_default();
+    match keyword {
+        "if" | "for" | "foreach" | "while" | "switch" | "lock" | "try" | "do" => true,
+        "using" => code[keyword.len()..].trim_start().starts_with('('),
+        "section" => at_sign,
+        _ => false,
+    }
+}
+
+/// Get length of Razor code starting with `@` in raw text of `<script>` or `<style>`,
+/// and whether it's a statement (code block, control structure or comment) rather than expression.
+/// Escaped `@@` is treated as expression.
+pub(crate) fn razor_code_len(code: &str) -> Option<(usize, bool)> {
+    let rest = code.strip_prefix('@')?;
+    if rest.starts_with('@') {
+        return Some((2, false));
+    }
+    if !is_razor_transition(code, 0) {
+        return None;
+    }
+    if let Some(comment) = rest.strip_prefix('*') {
+        return comment.find("*@").map(|i| (i + "@**@".len(), true));
+    }
+    if let Some(block) = rest.strip_prefix('{') {
+        return scan_csharp(block, &['}'])
+            .filter(|i| block[*i..].starts_with('}'))
+            .map(|i| (i + "@{}".len(), true));
+    }
+    if is_razor_control_flow(rest, true) {
+        let keyword = rest
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap_or_default();
+        let mut end = 1;
+        loop {
+            let header_end = scan_csharp(&code[end..], &['{', ';'])
+                .map(|i| end + i)
+                .filter(|i| code[*i..].starts_with('{'))?;
+            let body = &code[header_end + 1..];
+            end = scan_csharp(body, &['}'])
+                .filter(|i| body[*i..].starts_with('}'))
+                .map(|i| header_end + i + 2)?;
+
+            let trimmed = code[end..].trim_start();
+            let next_keyword = trimmed
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .next()
+                .unwrap_or_default();
+            match (keyword, next_keyword) {
+                ("if", "else") | ("try", "catch" | "finally") => {
+                    end = code.len() - trimmed.len();
+                }
+                ("do", "while") => {
+                    let trailer_start = code.len() - trimmed.len();
+                    let end = scan_csharp(trimmed, &[';'])
+                        .filter(|i| trimmed[*i..].starts_with(';'))
+                        .map_or(end, |i| trailer_start + i + 1);
+                    return Some((end, true));
+                }
+                _ => return Some((end, true)),
+            }
+        }
+    }
+    Some(razor_expr_len(rest))
+        .filter(|len| *len > 0)
+        .map(|len| (len + 1, false))
+}
+
+/// Get length of Razor explicit expression like `(a + b)`
+/// or implicit expression like `Model.Items[0].Name` and `await GetAsync()`, after `@`.
+fn razor_expr_len(code: &str) -> usize {
+    fn ident_len(code: &str) -> usize {
+        code.find(|c: char| !c.is_alphanumeric() && c != '_')
+            .unwrap_or(code.len())
+    }
+
+    if let Some(inner) = code.strip_prefix('(') {
+        return scan_csharp(inner, &[')'])
+            .filter(|i| inner[*i..].starts_with(')'))
+            .map_or(0, |i| i + 2);
+    }
+
+    let mut end = if code.starts_with("await ") {
+        "await ".len()
+    } else {
+        0
+    };
+    let len = ident_len(&code[end..]);
+    if len == 0 {
+        return 0;
+    }
+    end += len;
+    loop {
+        let rest = &code[end..];
+        if let Some((inner, close)) = rest
+            .strip_prefix('(')
+            .map(|inner| (inner, ')'))
+            .or_else(|| rest.strip_prefix('[').map(|inner| (inner, ']')))
+        {
+            match scan_csharp(inner, &[close]) {
+                Some(i) if inner[i..].starts_with(close) => end += i + 2,
+                _ => break,
+            }
+        } else if let Some(member) = rest.strip_prefix("?.").or_else(|| rest.strip_prefix('.')) {
+            if !member.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+                // period at the end of sentence
+                break;
+            }
+            end += rest.len() - member.len() + ident_len(member);
+        } else {
+            break;
+        }
+    }
+    end
+}
+
+/// Find the first character of `stops` at top level of C# code, skipping strings and comments.
+/// It also stops at unmatched closing bracket.
+fn scan_csharp(code: &str, stops: &[char]) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut chars = code.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if depth == 0 && stops.contains(&c) {
+            return Some(i);
+        }
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                if depth == 0 {
+                    return Some(i);
+                }
+                depth -= 1;
+            }
+            '"' => {
+                let verbatim = code[..i].ends_with('@') || code[..i].ends_with("@$");
+                while let Some((_, next)) = chars.next() {
+                    if next == '\\' && !verbatim {
+                        chars.next();
+                    } else if next == '"' {
+                        // `""` is escaped quote in verbatim string
+                        if !verbatim || chars.next_if(|(_, c)| *c == '"').is_none() {
+                            break;
+                        }
+                    }
+                }
+            }
+            '\'' => {
+                while let Some((_, next)) = chars.next() {
+                    if next == '\\' {
+                        chars.next();
+                    } else if next == '\'' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.next_if(|(_, c)| *c == '/').is_some() => {
+                while chars.next_if(|(_, c)| *c != '\n').is_some() {}
+            }
+            '/' if chars.next_if(|(_, c)| *c == '*').is_some() => {
+                while let Some((_, next)) = chars.next() {
+                    if next == '*' && chars.next_if(|(_, c)| *c == '/').is_some() {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Get length of PHP tag like `<?php code ?>` or `<?= expr ?>` in raw text of `<script>` or `<style>`,
+/// and whether it's a statement rather than expression.
+/// `<?php echo expr ?>` is treated as expression as well.
+pub(crate) fn php_tag_len(code: &str) -> Option<(usize, bool)> {
+    if !helpers::is_php_tag_start(code) {
+        return None;
+    }
+    let echo = code.starts_with("<?=");
+    let code_start = if echo { "<?=".len() } else { "<?php".len() };
+    let end = code_start + find_php_tag_end(&code[code_start..])? + "?>".len();
+    let is_stmt = !echo
+        && !code[code_start..]
+            .trim_start()
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .is_some_and(|word| {
+                word.eq_ignore_ascii_case("echo") || word.eq_ignore_ascii_case("print")
+            });
+    Some((end, is_stmt))
+}
+
+/// Find position of `?>` which ends PHP code, skipping strings and comments.
+fn find_php_tag_end(code: &str) -> Option<usize> {
+    let mut chars = code.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '?' if chars.next_if(|(_, c)| *c == '>').is_some() => return Some(i),
+            '\'' | '"' | '`' => {
+                while let Some((_, next)) = chars.next() {
+                    if next == '\\' {
+                        chars.next();
+                    } else if next == c {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.next_if(|(_, c)| *c == '*').is_some() => {
+                while let Some((_, next)) = chars.next() {
+                    if next == '*' && chars.next_if(|(_, c)| *c == '/').is_some() {
+                        break;
+                    }
+                }
+            }
+            '#' | '/' if c == '#' || chars.next_if(|(_, c)| *c == '/').is_some() => {
+                // single line comment is ended by `?>` as well
+                while let Some((i, next)) = chars.peek() {
+                    if *next == '\n' || *next == '?' && code[*i..].starts_with("?>") {
+                        break;
+                    }
+                    chars.next();
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Get name of Blade directive if `@` at the given position starts a directive.
+/// Other `@`, such as email addresses and social media handles, are just text.
+fn parse_blade_directive_name_at(source: &str, i: usize) -> Option<&str> {
+    if source[..i].ends_with(|c: char| c.is_alphanumeric() || c == '_' || c == '@') {
+        return None;
+    }
+    let rest = source.get(i..)?.strip_prefix('@')?;
+    let name = rest
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .next()
+        .filter(|name| name.starts_with(|c: char| c.is_ascii_alphabetic()))?;
+    if is_blade_builtin_directive(name)
+        || name.starts_with("end")
+        || name.starts_with("else")
+        || find_blade_end_directive(rest, name).is_some()
+    {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+/// Find position of `@end<name>` directive which closes Blade block directive.
+fn find_blade_end_directive(source: &str, name: &str) -> Option<usize> {
+    source.match_indices("@end").find_map(|(index, _)| {
+        source[index + "@end".len()..]
+            .strip_prefix(name)
+            .filter(|rest| !rest.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_'))
+            .map(|_| index)
+    })
+}
+
+/// Blade directives which don't have end directive.
+fn is_blade_builtin_directive(name: &str) -> bool {
+    matches!(
+        name,
+        "append"
+            | "aware"
+            | "break"
+            | "case"
+            | "checked"
+            | "choice"
+            | "class"
+            | "continue"
+            | "csrf"
+            | "dd"
+            | "default"
+            | "disabled"
+            | "dump"
+            | "each"
+            | "empty"
+            | "extends"
             | "include"
-            | "layout"
-            | "async"
-            | "function"
-            | "import"
-            | "export"
+            | "includeFirst"
+            | "includeIf"
+            | "includeUnless"
+            | "includeWhen"
+            | "inject"
+            | "js"
+            | "json"
+            | "lang"
+            | "method"
+            | "overwrite"
+            | "parent"
+            | "php"
+            | "props"
+            | "readonly"
+            | "required"
+            | "section"
+            | "selected"
+            | "show"
+            | "stack"
+            | "stop"
+            | "style"
+            | "use"
+            | "vite"
+            | "yield"
     )
 }
 
@@ -2590,6 +4583,7 @@ trait HasJinjaFlowControl<'s>: Sized {
     fn build(intermediate: Self::Intermediate, raw: &'s str) -> Self;
     fn from_tag(tag: JinjaTag<'s>) -> Self::Intermediate;
     fn from_block(block: JinjaBlock<'s, Self>) -> Self::Intermediate;
+    fn from_text(text: TextNode<'s>) -> Option<Self>;
 }
 
 impl<'s> HasJinjaFlowControl<'s> for Node<'s> {
@@ -2609,6 +4603,13 @@ impl<'s> HasJinjaFlowControl<'s> for Node<'s> {
     fn from_block(block: JinjaBlock<'s, Self>) -> Self::Intermediate {
         NodeKind::JinjaBlock(block)
     }
+
+    fn from_text(text: TextNode<'s>) -> Option<Self> {
+        Some(Node {
+            raw: text.raw,
+            kind: NodeKind::Text(text),
+        })
+    }
 }
 
 impl<'s> HasJinjaFlowControl<'s> for Attribute<'s> {
@@ -2625,4 +4626,8 @@ impl<'s> HasJinjaFlowControl<'s> for Attribute<'s> {
     fn from_block(block: JinjaBlock<'s, Self>) -> Self::Intermediate {
         Attribute::JinjaBlock(block)
     }
+
+    fn from_text(_: TextNode<'s>) -> Option<Self> {
+        None
+    }
 }
@@ -3,11 +3,12 @@ use crate::{
     config::{Quotes, VSlotStyle, WhitespaceSensitivity},
     ctx::{Ctx, Hints, NestWithCtx},
     helpers,
+    parser::{self, parse_jinja_tag_name},
     state::State,
     Language,
 };
 use itertools::Itertools;
-use std::borrow::Cow;
+use std::{borrow::Cow, mem};
 use tiny_pretty::Doc;
 
 pub(super) trait DocGen<'s> {
@@ -303,15 +304,146 @@ impl<'s> DocGen<'s> for Attribute<'s> {
         match self {
             Attribute::Native(native_attribute) => native_attribute.doc(ctx, state),
             Attribute::Svelte(svelte_attribute) => svelte_attribute.doc(ctx, state),
+            Attribute::SvelteAttachment(svelte_attachment) => svelte_attachment.doc(ctx, state),
             Attribute::VueDirective(vue_directive) => vue_directive.doc(ctx, state),
             Attribute::Astro(astro_attribute) => astro_attribute.doc(ctx, state),
+            Attribute::BladeDirectiveOrBlock(blade_directive_or_block) => {
+                blade_directive_or_block.doc(ctx, state)
+            }
+            Attribute::ErbTagOrBlock(erb_tag_or_block) => erb_tag_or_block.doc(ctx, state),
+            Attribute::GoTemplateTagOrBlock(go_template_tag_or_block) => {
+                go_template_tag_or_block.doc(ctx, state)
+            }
+            Attribute::HandlebarsTagOrBlock(handlebars_tag_or_block) => {
+                handlebars_tag_or_block.doc(ctx, state)
+            }
             Attribute::JinjaBlock(jinja_block) => jinja_block.doc(ctx, state),
+            Attribute::PhpTagOrBlock(php_tag_or_block) => php_tag_or_block.doc(ctx, state),
             Attribute::JinjaTag(jinja_tag) => jinja_tag.doc(ctx, state),
             Attribute::VentoTagOrBlock(vento_tag_or_block) => vento_tag_or_block.doc(ctx, state),
         }
     }
 }
 
+impl<'s> DocGen<'s> for BladeBlock<'s> {
+    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, state: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        if let [BladeDirectiveOrChildren::Directive(first), .., BladeDirectiveOrChildren::Directive(last)] =
+            &self.body[..]
+        {
+            if matches!(first.name, "php" | "verbatim") && first.args.is_none() {
+                let mut docs = Vec::with_capacity(5);
+                docs.push(first.doc(ctx, state));
+                if let Some(BladeDirectiveOrChildren::Children(children)) = self.body.get(1) {
+                    if let [Node {
+                        kind: NodeKind::Text(text_node),
+                        ..
+                    }] = &children[..]
+                    {
+                        if text_node.raw.chars().all(|c| c.is_ascii_whitespace()) {
+                            docs.push(Doc::hard_line());
+                        } else if first.name == "php" {
+                            let formatted = ctx.format_php(text_node.raw, text_node.start);
+                            docs.push(
+                                Doc::hard_line()
+                                    .concat(reflow_with_indent(&formatted))
+                                    .nest_with_ctx(ctx),
+                            );
+                            docs.push(Doc::hard_line());
+                        } else {
+                            docs.extend(reflow_raw(text_node.raw));
+                        }
+                    }
+                }
+                docs.push(last.doc(ctx, state));
+                return Doc::list(docs);
+            }
+        }
+
+        Doc::list(
+            self.body
+                .iter()
+                .map(|child| match child {
+                    BladeDirectiveOrChildren::Directive(directive) => directive.doc(ctx, state),
+                    BladeDirectiveOrChildren::Children(children) => {
+                        format_control_structure_block_children(children, ctx, state)
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<'s> DocGen<'s> for BladeComment<'s> {
+    fn doc<E, F>(&self, _: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        Doc::text("{{--")
+            .concat(reflow_raw(self.raw))
+            .append(Doc::text("--}}"))
+    }
+}
+
+impl<'s> DocGen<'s> for BladeDirective<'s> {
+    fn doc<E, F>(&self, _: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        let directive = Doc::text("@").append(Doc::text(self.name));
+        if let Some((args, _)) = self.args {
+            // Laravel writes PHP control structures like `@if ($x)`, but others like `@include('x')`.
+            let is_control_structure = matches!(
+                self.name,
+                "if" | "elseif" | "unless" | "for" | "foreach" | "forelse" | "while"
+            );
+            directive
+                .append(if is_control_structure {
+                    Doc::text(" (")
+                } else {
+                    Doc::text("(")
+                })
+                .concat(reflow_raw(args.trim()))
+                .append(Doc::text(")"))
+        } else {
+            directive
+        }
+    }
+}
+
+impl<'s> DocGen<'s> for BladeEcho<'s> {
+    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        let (open, close) = if self.escaped {
+            ("{{", "}}")
+        } else {
+            ("{!!", "!!}")
+        };
+        Doc::text(open)
+            .append(Doc::line_or_space())
+            .append(Doc::text(self.expr.trim()))
+            .nest(ctx.indent_width)
+            .append(Doc::line_or_space())
+            .append(Doc::text(close))
+            .group()
+    }
+}
+
+impl<'s> DocGen<'s> for Cdata<'s> {
+    fn doc<E, F>(&self, _: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        Doc::text("<![CDATA[")
+            .concat(reflow_raw(self.raw))
+            .append(Doc::text("]]>"))
+    }
+}
+
 impl<'s> DocGen<'s> for Comment<'s> {
     fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
     where
@@ -342,6 +474,8 @@ impl<'s> DocGen<'s> for Doctype<'s> {
 
         Doc::text("<!")
             .append(match ctx.options.doctype_keyword_case {
+                // keyword must be uppercase in XML
+                _ if matches!(ctx.language, Language::Xml) => Doc::text(self.keyword),
                 DoctypeKeywordCase::Ignore => Doc::text(self.keyword),
                 DoctypeKeywordCase::Upper => Doc::text("DOCTYPE"),
                 DoctypeKeywordCase::Lower => Doc::text("doctype"),
@@ -374,7 +508,16 @@ impl<'s> DocGen<'s> for Element<'s> {
         };
         let should_lower_cased = matches!(
             ctx.language,
-            Language::Html | Language::Jinja | Language::Vento
+            Language::Html
+                | Language::Jinja
+                | Language::Vento
+                | Language::Liquid
+                | Language::GoTemplate
+                | Language::Erb
+                | Language::Eex
+                | Language::Blade
+                | Language::Php
+                | Language::Mjml
         ) && css_dataset::tags::STANDARD_HTML_TAGS
             .iter()
             .any(|tag| tag.eq_ignore_ascii_case(self.tag_name));
@@ -418,6 +561,11 @@ impl<'s> DocGen<'s> for Element<'s> {
             Cow::from(self.tag_name)
         }));
 
+        let attrs = if matches!(ctx.language, Language::Astro) {
+            sort_astro_client_directives(&self.attrs)
+        } else {
+            self.attrs.iter().collect()
+        };
         let attrs_sep = if !self.first_attr_same_line
             && !ctx.options.prefer_attrs_single_line
             && self.attrs.len() > 1
@@ -433,13 +581,13 @@ impl<'s> DocGen<'s> for Element<'s> {
         };
         let attrs = if let Some(max) = ctx.options.max_attrs_per_line {
             // fix #2
-            if self.attrs.is_empty() {
+            if attrs.is_empty() {
                 Doc::line_or_nil()
             } else {
                 Doc::line_or_space()
             }
             .concat(itertools::intersperse(
-                self.attrs.chunks(max.into()).map(|chunk| {
+                attrs.chunks(max.into()).map(|chunk| {
                     Doc::list(
                         itertools::intersperse(
                             chunk.iter().map(|attr| attr.doc(ctx, &state)),
@@ -454,7 +602,7 @@ impl<'s> DocGen<'s> for Element<'s> {
             .nest_with_ctx(ctx)
         } else {
             Doc::list(
-                self.attrs
+                attrs
                     .iter()
                     .flat_map(|attr| [attrs_sep.clone(), attr.doc(ctx, &state)].into_iter())
                     .collect(),
@@ -481,7 +629,42 @@ impl<'s> DocGen<'s> for Element<'s> {
             docs.push(Doc::text("/>"));
             return Doc::list(docs).group();
         }
-        if ctx.options.closing_bracket_same_line {
+        let mjml_content = if matches!(ctx.language, Language::Mjml) {
+            helpers::get_mjml_content(tag_name)
+        } else {
+            None
+        };
+        // `mj-raw` content is kept as is, so don't break its brackets.
+        let is_mjml_raw = matches!(mjml_content, Some(helpers::MjmlContent::Raw));
+        let is_non_compiled = helpers::has_non_compiled_attr(&self.attrs, &ctx.language);
+        // Raw text elements in SVG are parsed as normal elements,
+        // so it's only treated as raw text when there's only one text child.
+        let is_raw_text = helpers::is_raw_text_element(tag_name, ctx.language.clone())
+            && matches!(
+                &self.children[..],
+                [Node {
+                    kind: NodeKind::Text(..),
+                    ..
+                }]
+            );
+        let is_xml_text = matches!(ctx.language, Language::Xml)
+            && matches!(
+                &self.children[..],
+                [Node {
+                    kind: NodeKind::Text(text_node),
+                    ..
+                }] if !text_node.raw.trim().is_empty()
+            );
+        let is_verbatim = is_non_compiled || is_raw_text || is_xml_text;
+        // `plaintext` element may have no end tag, then its text content reaches the end.
+        let is_plaintext_without_end_tag = is_raw_text
+            && tag_name.eq_ignore_ascii_case("plaintext")
+            && matches!(
+                &self.children[..],
+                [Node { kind: NodeKind::Text(text_node), .. }]
+                    if text_node.start + text_node.raw.len() == ctx.source.len()
+            );
+        if ctx.options.closing_bracket_same_line || is_mjml_raw {
             docs.push(attrs.append(Doc::text(">")).group());
         } else {
             // for #16
@@ -542,7 +725,8 @@ impl<'s> DocGen<'s> for Element<'s> {
             )
         };
 
-        if tag_name.eq_ignore_ascii_case("script") {
+        let is_xml = matches!(ctx.language, Language::Xml);
+        if tag_name.eq_ignore_ascii_case("script") && !is_xml {
             if let [Node {
                 kind: NodeKind::Text(text_node),
                 ..
@@ -567,26 +751,38 @@ impl<'s> DocGen<'s> for Element<'s> {
                         }
                     });
                     let formatted = if is_json {
-                        ctx.format_json(text_node.raw, text_node.start)
-                    } else {
-                        ctx.format_script(
+                        format_with_template_tags(
                             text_node.raw,
-                            self.attrs
-                                .iter()
-                                .find_map(|attr| match attr {
-                                    Attribute::Native(native_attribute)
-                                        if native_attribute.name.eq_ignore_ascii_case("lang") =>
-                                    {
-                                        native_attribute.value.map(|(value, _)| value)
-                                    }
-                                    _ => None,
-                                })
-                                .unwrap_or(if matches!(ctx.language, Language::Astro) {
+                            EmbeddedCode::Json,
+                            ctx,
+                            |ctx, code| ctx.format_json(code, text_node.start).into_owned(),
+                        )
+                    } else {
+                        let lang = self
+                            .attrs
+                            .iter()
+                            .find_map(|attr| match attr {
+                                Attribute::Native(native_attribute)
+                                    if native_attribute.name.eq_ignore_ascii_case("lang") =>
+                                {
+                                    native_attribute.value.map(|(value, _)| value)
+                                }
+                                _ => None,
+                            })
+                            .unwrap_or(
+                                if matches!(ctx.language, Language::Astro)
+                                    && !is_astro_inline_script(&self.attrs)
+                                {
                                     "ts"
                                 } else {
                                     "js"
-                                }),
-                            text_node.start,
+                                },
+                            );
+                        format_with_template_tags(
+                            text_node.raw,
+                            EmbeddedCode::Script,
+                            ctx,
+                            |ctx, code| ctx.format_script(code, lang, text_node.start).into_owned(),
                         )
                     };
                     let doc = Doc::hard_line().concat(reflow_with_indent(formatted.trim()));
@@ -600,7 +796,9 @@ impl<'s> DocGen<'s> for Element<'s> {
                     );
                 }
             }
-        } else if tag_name.eq_ignore_ascii_case("style") {
+        } else if tag_name.eq_ignore_ascii_case("style") && !is_xml
+            || matches!(mjml_content, Some(helpers::MjmlContent::Css))
+        {
             if let [Node {
                 kind: NodeKind::Text(text_node),
                 ..
@@ -609,20 +807,23 @@ impl<'s> DocGen<'s> for Element<'s> {
                 if text_node.raw.chars().all(|c| c.is_ascii_whitespace()) {
                     docs.push(Doc::hard_line());
                 } else {
-                    let formatted = ctx.format_style(
+                    let lang = self
+                        .attrs
+                        .iter()
+                        .find_map(|attr| match attr {
+                            Attribute::Native(native_attribute)
+                                if native_attribute.name.eq_ignore_ascii_case("lang") =>
+                            {
+                                native_attribute.value.map(|(value, _)| value)
+                            }
+                            _ => None,
+                        })
+                        .unwrap_or("css");
+                    let formatted = format_with_template_tags(
                         text_node.raw,
-                        self.attrs
-                            .iter()
-                            .find_map(|attr| match attr {
-                                Attribute::Native(native_attribute)
-                                    if native_attribute.name.eq_ignore_ascii_case("lang") =>
-                                {
-                                    native_attribute.value.map(|(value, _)| value)
-                                }
-                                _ => None,
-                            })
-                            .unwrap_or("css"),
-                        text_node.start,
+                        EmbeddedCode::Style,
+                        ctx,
+                        |ctx, code| ctx.format_style(code, lang, text_node.start).into_owned(),
                     );
                     let doc = Doc::hard_line().concat(reflow_with_indent(formatted.trim()));
                     docs.push(
@@ -635,20 +836,32 @@ impl<'s> DocGen<'s> for Element<'s> {
                     );
                 }
             }
-        } else if tag_name.eq_ignore_ascii_case("pre") || tag_name.eq_ignore_ascii_case("textarea")
+        } else if (tag_name.eq_ignore_ascii_case("pre")
+            || tag_name.eq_ignore_ascii_case("textarea"))
+            && !is_xml
+            || is_mjml_raw
+            || is_verbatim
         {
             if let [Node {
                 kind: NodeKind::Text(text_node),
                 ..
             }] = &self.children[..]
             {
-                if text_node.raw.contains('\n')
+                if is_verbatim || is_mjml_raw {
+                    // This prints nothing, but it stops measuring raw text
+                    // when checking if the opening tag fits.
+                    docs.push(Doc::nil().union(Doc::hard_line()));
+                } else if text_node.raw.contains('\n')
                     && !text_node.raw.starts_with('\n')
                     && !text_node.raw.starts_with("\r\n")
                 {
                     docs.push(Doc::empty_line());
                 }
-                docs.extend(reflow_raw(text_node.raw));
+                docs.extend(reflow_raw(if is_plaintext_without_end_tag {
+                    text_node.raw.trim_end()
+                } else {
+                    text_node.raw
+                }));
             }
         } else if is_empty {
             use crate::config::ClosingTagLineBreakForEmpty;
@@ -687,6 +900,10 @@ impl<'s> DocGen<'s> for Element<'s> {
                             | NodeKind::AstroExpr(..)
                             | NodeKind::JinjaInterpolation(..)
                             | NodeKind::VentoInterpolation(..)
+                            | NodeKind::HandlebarsInterpolation(..)
+                            | NodeKind::GoTemplateInterpolation(..)
+                            | NodeKind::BladeEcho(..)
+                            | NodeKind::RazorExpr(..)
                     )
                 })
             {
@@ -703,6 +920,10 @@ impl<'s> DocGen<'s> for Element<'s> {
             docs.push(trailing_ws);
         }
 
+        if is_plaintext_without_end_tag {
+            return Doc::list(docs).group();
+        }
+
         docs.push(
             Doc::text("</")
                 .append(Doc::text(if should_lower_cased {
@@ -710,7 +931,11 @@ impl<'s> DocGen<'s> for Element<'s> {
                 } else {
                     Cow::from(self.tag_name)
                 }))
-                .append(Doc::line_or_nil())
+                .append(if is_mjml_raw || is_verbatim {
+                    Doc::nil()
+                } else {
+                    Doc::line_or_nil()
+                })
                 .append(Doc::text(">"))
                 .group(),
         );
@@ -732,14 +957,29 @@ impl<'s> DocGen<'s> for FrontMatter<'s> {
                 .append(Doc::hard_line())
                 .append(Doc::text("---"))
         } else {
-            Doc::text("---")
-                .concat(reflow_raw(self.raw))
-                .append(Doc::text("---"))
+            let (delimiter, ext) = if self.toml {
+                ("+++", "toml")
+            } else {
+                ("---", "yaml")
+            };
+            let formatted = ctx.format_script(self.raw, ext, self.start);
+            let formatted = formatted.trim();
+            if formatted.is_empty() {
+                Doc::text(delimiter)
+                    .append(Doc::hard_line())
+                    .append(Doc::text(delimiter))
+            } else {
+                Doc::text(delimiter)
+                    .append(Doc::hard_line())
+                    .concat(reflow_with_indent(formatted))
+                    .append(Doc::hard_line())
+                    .append(Doc::text(delimiter))
+            }
         }
     }
 }
 
-impl<'s> DocGen<'s> for JinjaBlock<'s, Attribute<'s>> {
+impl<'s> DocGen<'s> for ErbBlock<'s> {
     fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, state: &State<'s>) -> Doc<'s>
     where
         F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
@@ -748,21 +988,77 @@ impl<'s> DocGen<'s> for JinjaBlock<'s, Attribute<'s>> {
             self.body
                 .iter()
                 .map(|child| match child {
-                    JinjaTagOrChildren::Tag(tag) => tag.doc(ctx, state),
-                    JinjaTagOrChildren::Children(children) => Doc::line_or_nil()
-                        .concat(itertools::intersperse(
-                            children.iter().map(|attr| attr.doc(ctx, state)),
-                            Doc::line_or_space(),
-                        ))
-                        .nest_with_ctx(ctx)
-                        .append(Doc::line_or_nil()),
+                    ErbTagOrChildren::Tag(tag) => tag.doc(ctx, state),
+                    ErbTagOrChildren::Children(children) => {
+                        format_control_structure_block_children(children, ctx, state)
+                    }
                 })
                 .collect(),
         )
     }
 }
 
-impl<'s> DocGen<'s> for JinjaBlock<'s, Node<'s>> {
+impl<'s> DocGen<'s> for ErbComment<'s> {
+    fn doc<E, F>(&self, _: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        if self.long {
+            Doc::text("<%!--")
+                .concat(reflow_raw(self.raw))
+                .append(Doc::text("--%>"))
+        } else {
+            Doc::text("<%#")
+                .concat(reflow_raw(self.raw))
+                .append(Doc::text("%>"))
+        }
+    }
+}
+
+impl<'s> DocGen<'s> for ErbTag<'s> {
+    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        let open = Doc::text("<%").append(Doc::text(self.prefix));
+        let close = Doc::text(if self.trim_next { "-%>" } else { "%>" });
+        if self.code.trim().is_empty() {
+            return open.append(Doc::space()).append(close);
+        }
+
+        // Code of block tags like `<% if x %>` is incomplete, so it can't be formatted.
+        let code = if let helpers::TemplateTagKind::Other =
+            helpers::get_erb_tag_kind(self.code, &ctx.language)
+        {
+            let ext = if matches!(ctx.language, Language::Erb) {
+                "rb"
+            } else {
+                "ex"
+            };
+            ctx.format_script(self.code, ext, self.start)
+                .trim()
+                .to_owned()
+        } else {
+            self.code.trim().to_owned()
+        };
+        if code.contains('\n') {
+            open.append(
+                Doc::hard_line()
+                    .concat(reflow_with_indent(&code))
+                    .nest_with_ctx(ctx),
+            )
+            .append(Doc::hard_line())
+            .append(close)
+        } else {
+            open.append(Doc::space())
+                .append(Doc::text(code))
+                .append(Doc::space())
+                .append(close)
+        }
+    }
+}
+
+impl<'s> DocGen<'s> for GoTemplateBlock<'s> {
     fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, state: &State<'s>) -> Doc<'s>
     where
         F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
@@ -771,8 +1067,8 @@ impl<'s> DocGen<'s> for JinjaBlock<'s, Node<'s>> {
             self.body
                 .iter()
                 .map(|child| match child {
-                    JinjaTagOrChildren::Tag(tag) => tag.doc(ctx, state),
-                    JinjaTagOrChildren::Children(children) => {
+                    GoTemplateTagOrChildren::Tag(tag) => tag.doc(ctx, state),
+                    GoTemplateTagOrChildren::Children(children) => {
                         format_control_structure_block_children(children, ctx, state)
                     }
                 })
@@ -781,119 +1077,391 @@ impl<'s> DocGen<'s> for JinjaBlock<'s, Node<'s>> {
     }
 }
 
-impl<'s> DocGen<'s> for JinjaComment<'s> {
-    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+impl<'s> DocGen<'s> for GoTemplateComment<'s> {
+    fn doc<E, F>(&self, _: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
     where
         F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
     {
-        if ctx.options.format_comments {
-            Doc::text("{#")
-                .append(Doc::line_or_space())
-                .concat(reflow_with_indent(self.raw.trim()))
-                .nest_with_ctx(ctx)
-                .append(Doc::line_or_space())
-                .append(Doc::text("#}"))
-                .group()
-        } else {
-            Doc::text("{#")
-                .concat(reflow_raw(self.raw))
-                .append(Doc::text("#}"))
-        }
+        Doc::text(if self.trim_prev { "{{- /*" } else { "{{/*" })
+            .concat(reflow_raw(self.raw))
+            .append(Doc::text(if self.trim_next { "*/ -}}" } else { "*/}}" }))
     }
 }
 
-impl<'s> DocGen<'s> for JinjaInterpolation<'s> {
-    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+impl<'s> DocGen<'s> for GoTemplateInterpolation<'s> {
+    fn doc<E, F>(&self, _: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
     where
         F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
     {
-        Doc::text("{{")
-            .append(Doc::line_or_space())
-            .append(Doc::text(self.expr.trim()))
-            .nest(ctx.indent_width)
-            .append(Doc::line_or_space())
-            .append(Doc::text("}}"))
-            .group()
+        format_go_template_action(self.expr, self.trim_prev, self.trim_next)
     }
 }
 
-impl<'s> DocGen<'s> for JinjaTag<'s> {
-    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+impl<'s> DocGen<'s> for GoTemplateTag<'s> {
+    fn doc<E, F>(&self, _: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
     where
         F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
     {
-        let (prefix, content) = self
-            .content
-            .strip_prefix('-')
-            .map(|content| ("-", content))
-            .unwrap_or(("", self.content));
-        let (content, suffix) = self
-            .content
-            .strip_suffix('-')
-            .map(|content| (content, "-"))
-            .unwrap_or((content, ""));
-        Doc::text("{%")
-            .append(Doc::text(prefix))
-            .append(Doc::line_or_space())
-            .append(Doc::text(content.trim()))
-            .nest(ctx.indent_width)
-            .append(Doc::line_or_space())
-            .append(Doc::text(suffix))
-            .append(Doc::text("%}"))
-            .group()
+        format_go_template_action(self.tag, self.trim_prev, self.trim_next)
     }
 }
 
-impl<'s> DocGen<'s> for NativeAttribute<'s> {
+impl<'s> DocGen<'s> for HandlebarsBlock<'s> {
     fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, state: &State<'s>) -> Doc<'s>
     where
         F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
     {
-        let name = Doc::text(self.name);
-        if let Some((value, value_start)) = self.value {
-            let value = match ctx.language {
-                Language::Vue => {
-                    if state
-                        .current_tag_name
-                        .map(|name| name.eq_ignore_ascii_case("script"))
-                        .unwrap_or_default()
-                        && self.name == "generic"
-                    {
-                        Cow::from(ctx.format_type_params(value, value_start))
-                    } else {
-                        Cow::from(value)
-                    }
-                }
-                Language::Svelte if !ctx.options.strict_svelte_attr => {
-                    if let Some(expr) = value
-                        .strip_prefix('{')
-                        .and_then(|s| s.strip_suffix('}'))
-                        .filter(|s| !s.contains('{'))
-                    {
-                        let formatted_expr = ctx.format_expr(expr, false, value_start);
-                        return match self.name.split_once(':') {
-                            Some((_, name))
-                                if matches!(ctx.options.svelte_directive_shorthand, Some(true))
-                                    && name == formatted_expr =>
-                            {
-                                Doc::text(self.name)
-                            }
-                            None if matches!(ctx.options.svelte_attr_shorthand, Some(true))
-                                && self.name == formatted_expr =>
-                            {
-                                Doc::text("{")
-                                    .concat(reflow_with_indent(&formatted_expr))
-                                    .append(Doc::text("}"))
-                            }
-                            _ => Doc::text(self.name.to_owned())
-                                .append(Doc::text("={"))
-                                .concat(reflow_with_indent(&formatted_expr))
-                                .append(Doc::text("}")),
-                        };
-                    } else {
-                        Cow::from(value)
+        Doc::list(
+            self.body
+                .iter()
+                .map(|child| match child {
+                    HandlebarsTagOrChildren::Tag(tag) => tag.doc(ctx, state),
+                    HandlebarsTagOrChildren::Children(children) => {
+                        format_control_structure_block_children(children, ctx, state)
                     }
-                }
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<'s> DocGen<'s> for HandlebarsComment<'s> {
+    fn doc<E, F>(&self, _: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        if self.long {
+            Doc::text("{{!--")
+                .concat(reflow_raw(self.raw))
+                .append(Doc::text("--}}"))
+        } else {
+            Doc::text("{{!")
+                .concat(reflow_raw(self.raw))
+                .append(Doc::text("}}"))
+        }
+    }
+}
+
+impl<'s> DocGen<'s> for HandlebarsInterpolation<'s> {
+    fn doc<E, F>(&self, _: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        let (open, close) = if self.triple {
+            ("{{{", "}}}")
+        } else {
+            ("{{", "}}")
+        };
+        Doc::text(open)
+            .append(if self.trim_prev {
+                Doc::text("~")
+            } else {
+                Doc::nil()
+            })
+            .append(Doc::text(format_handlebars_expr(self.expr)))
+            .append(if self.trim_next {
+                Doc::text("~")
+            } else {
+                Doc::nil()
+            })
+            .append(Doc::text(close))
+    }
+}
+
+impl<'s> DocGen<'s> for HandlebarsTag<'s> {
+    fn doc<E, F>(&self, _: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        let tag = self.tag.trim();
+        let (sigil, rest) = ["#>", "#*", "#", "^", "/", ">"]
+            .iter()
+            .find_map(|sigil| tag.strip_prefix(sigil).map(|rest| (*sigil, rest)))
+            .unwrap_or(("", tag));
+        Doc::text("{{")
+            .append(if self.trim_prev {
+                Doc::text("~")
+            } else {
+                Doc::nil()
+            })
+            .append(Doc::text(sigil))
+            .append(if sigil.ends_with('>') && !rest.trim().is_empty() {
+                Doc::space()
+            } else {
+                Doc::nil()
+            })
+            .append(Doc::text(format_handlebars_expr(rest)))
+            .append(if self.trim_next {
+                Doc::text("~")
+            } else {
+                Doc::nil()
+            })
+            .append(Doc::text("}}"))
+    }
+}
+
+impl<'s> DocGen<'s> for JinjaBlock<'s, Attribute<'s>> {
+    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, state: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        Doc::list(
+            self.body
+                .iter()
+                .map(|child| match child {
+                    JinjaTagOrChildren::Tag(tag) => tag.doc(ctx, state),
+                    JinjaTagOrChildren::Children(children) => Doc::line_or_nil()
+                        .concat(itertools::intersperse(
+                            children.iter().map(|attr| attr.doc(ctx, state)),
+                            Doc::line_or_space(),
+                        ))
+                        .nest_with_ctx(ctx)
+                        .append(Doc::line_or_nil()),
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<'s> DocGen<'s> for JinjaBlock<'s, Node<'s>> {
+    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, state: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        if let (
+            Language::Liquid,
+            [JinjaTagOrChildren::Tag(first_tag), .., JinjaTagOrChildren::Tag(last_tag)],
+        ) = (&ctx.language, &self.body[..])
+        {
+            let tag_name = parse_jinja_tag_name(first_tag);
+            if helpers::is_liquid_raw_block(tag_name) {
+                let mut docs = Vec::with_capacity(5);
+                docs.push(first_tag.doc(ctx, state));
+                if let Some(JinjaTagOrChildren::Children(children)) = self.body.get(1) {
+                    if let [Node {
+                        kind: NodeKind::Text(text_node),
+                        ..
+                    }] = &children[..]
+                    {
+                        if text_node.raw.chars().all(|c| c.is_ascii_whitespace()) {
+                            docs.push(Doc::hard_line());
+                        } else {
+                            let formatted = match tag_name {
+                                "schema" => Some((
+                                    ctx.format_json(text_node.raw, text_node.start),
+                                    ctx.script_indent(),
+                                )),
+                                "javascript" => Some((
+                                    ctx.format_script(text_node.raw, "js", text_node.start),
+                                    ctx.script_indent(),
+                                )),
+                                "stylesheet" => Some((
+                                    ctx.format_style(text_node.raw, "css", text_node.start),
+                                    ctx.style_indent(),
+                                )),
+                                _ => None,
+                            };
+                            if let Some((formatted, indent)) = formatted {
+                                let doc =
+                                    Doc::hard_line().concat(reflow_with_indent(formatted.trim()));
+                                docs.push(if indent { doc.nest_with_ctx(ctx) } else { doc });
+                                docs.push(Doc::hard_line());
+                            } else {
+                                docs.extend(reflow_raw(text_node.raw));
+                            }
+                        }
+                    }
+                }
+                docs.push(last_tag.doc(ctx, state));
+                return Doc::list(docs);
+            }
+        }
+
+        Doc::list(
+            self.body
+                .iter()
+                .map(|child| match child {
+                    JinjaTagOrChildren::Tag(tag) => tag.doc(ctx, state),
+                    JinjaTagOrChildren::Children(children) => {
+                        format_control_structure_block_children(children, ctx, state)
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<'s> DocGen<'s> for JinjaComment<'s> {
+    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        if ctx.options.format_comments {
+            Doc::text("{#")
+                .append(Doc::line_or_space())
+                .concat(reflow_with_indent(self.raw.trim()))
+                .nest_with_ctx(ctx)
+                .append(Doc::line_or_space())
+                .append(Doc::text("#}"))
+                .group()
+        } else {
+            Doc::text("{#")
+                .concat(reflow_raw(self.raw))
+                .append(Doc::text("#}"))
+        }
+    }
+}
+
+impl<'s> DocGen<'s> for JinjaInterpolation<'s> {
+    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        let (prefix, expr, suffix) = helpers::split_jinja_whitespace_control(self.expr);
+        Doc::text("{{")
+            .append(Doc::text(prefix))
+            .append(Doc::line_or_space())
+            .append(Doc::text(expr.trim()))
+            .nest(ctx.indent_width)
+            .append(Doc::line_or_space())
+            .append(Doc::text(suffix))
+            .append(Doc::text("}}"))
+            .group()
+    }
+}
+
+impl<'s> DocGen<'s> for JinjaTag<'s> {
+    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        let (prefix, content, suffix) = helpers::split_jinja_whitespace_control(self.content);
+        Doc::text("{%")
+            .append(Doc::text(prefix))
+            .append(Doc::line_or_space())
+            .append(Doc::text(content.trim()))
+            .nest(ctx.indent_width)
+            .append(Doc::line_or_space())
+            .append(Doc::text(suffix))
+            .append(Doc::text("%}"))
+            .group()
+    }
+}
+
+impl<'s> DocGen<'s> for NativeAttribute<'s> {
+    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, state: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        let name = Doc::text(self.name);
+        if let Some((value, value_start)) = self.value {
+            if matches!(ctx.language, Language::Html) {
+                let formatted = if ctx.options.alpine {
+                    format_alpine_attr_value(self.name, value, value_start, ctx)
+                } else {
+                    None
+                };
+                let formatted = match formatted {
+                    None if ctx.options.htmx => {
+                        format_htmx_attr_value(self.name, value, value_start, ctx)
+                    }
+                    formatted => formatted,
+                };
+                let formatted = match formatted {
+                    None if ctx.options.aurelia => {
+                        format_aurelia_attr_value(self.name, value, value_start, ctx)
+                    }
+                    formatted => formatted,
+                };
+                if let Some(value) = formatted {
+                    return name.append(Doc::text("=")).append(format_attr_value(
+                        value,
+                        &ctx.options.quotes,
+                        ctx,
+                    ));
+                }
+            }
+            let mut is_mixed_value = false;
+            let value = match ctx.language {
+                Language::Vue => {
+                    if state
+                        .current_tag_name
+                        .map(|name| name.eq_ignore_ascii_case("script"))
+                        .unwrap_or_default()
+                        && self.name == "generic"
+                    {
+                        Cow::from(ctx.format_type_params(value, value_start))
+                    } else if self.name == "slot-scope" {
+                        Cow::from(ctx.with_escaping_quotes(value, |code, ctx| {
+                            ctx.format_binding(&code, value_start)
+                        }))
+                    } else {
+                        Cow::from(value)
+                    }
+                }
+                Language::Svelte if !ctx.options.strict_svelte_attr => {
+                    if let Some(expr) = value
+                        .strip_prefix('{')
+                        .and_then(|s| s.strip_suffix('}'))
+                        .filter(|s| !s.contains('{'))
+                    {
+                        let formatted_expr = ctx.format_expr(expr, false, value_start);
+                        return match self.name.split_once(':') {
+                            Some((_, name))
+                                if matches!(ctx.options.svelte_directive_shorthand, Some(true))
+                                    && name == formatted_expr =>
+                            {
+                                Doc::text(self.name)
+                            }
+                            None if matches!(ctx.options.svelte_attr_shorthand, Some(true))
+                                && self.name == formatted_expr =>
+                            {
+                                Doc::text("{")
+                                    .concat(reflow_with_indent(&formatted_expr))
+                                    .append(Doc::text("}"))
+                            }
+                            _ => Doc::text(self.name.to_owned())
+                                .append(Doc::text("={"))
+                                .concat(reflow_with_indent(&formatted_expr))
+                                .append(Doc::text("}")),
+                        };
+                    } else if let Some(formatted) = value
+                        .contains('{')
+                        .then(|| {
+                            format_svelte_mixed_attr_value(
+                                value,
+                                value_start,
+                                self.name.eq_ignore_ascii_case("class"),
+                                ctx,
+                            )
+                        })
+                        .flatten()
+                    {
+                        is_mixed_value = true;
+                        Cow::from(formatted)
+                    } else {
+                        Cow::from(value)
+                    }
+                }
+                Language::Heex if self.quote.is_none() && value.starts_with('{') => {
+                    // HEEx expression like `class={@class}` can't be quoted.
+                    return name.append(Doc::text("=")).append(Doc::text(value));
+                }
+                Language::Handlebars if self.quote.is_none() => {
+                    // Unquoted mustache passes value as is in Glimmer, so it mustn't be quoted.
+                    if let Some(expr) = value
+                        .strip_prefix("{{")
+                        .and_then(|s| s.strip_suffix("}}"))
+                        .filter(|s| !s.starts_with(['{', '~']) && !s.ends_with('~'))
+                    {
+                        return name
+                            .append(Doc::text("={{"))
+                            .append(Doc::text(format_handlebars_expr(expr)))
+                            .append(Doc::text("}}"));
+                    } else {
+                        Cow::from(value)
+                    }
+                }
                 Language::Angular
                     if self.name.starts_with(['[', '(']) && self.name.ends_with([']', ')']) =>
                 {
@@ -924,7 +1492,13 @@ impl<'s> DocGen<'s> for NativeAttribute<'s> {
             docs.push(name);
             docs.push(Doc::text("="));
             docs.push(quote.clone());
-            if self.name.eq_ignore_ascii_case("class") {
+            if is_mixed_value {
+                docs.extend(reflow_owned(&value));
+            } else if let Some(doc) = self.value.and_then(|(value, _)| {
+                format_template_attr_value(value, self.name.eq_ignore_ascii_case("class"), ctx)
+            }) {
+                docs.push(doc);
+            } else if self.name.eq_ignore_ascii_case("class") {
                 docs.push(Doc::text(value.split_ascii_whitespace().join(" ")));
             } else if self.name.eq_ignore_ascii_case("style") {
                 docs.push(Doc::text(ctx.format_style_attr(&value, value_start)));
@@ -967,16 +1541,46 @@ impl<'s> DocGen<'s> for NodeKind<'s> {
             NodeKind::AngularLet(angular_let) => angular_let.doc(ctx, state),
             NodeKind::AngularSwitch(angular_switch) => angular_switch.doc(ctx, state),
             NodeKind::AstroExpr(astro_expr) => astro_expr.doc(ctx, state),
+            NodeKind::Cdata(cdata) => cdata.doc(ctx, state),
             NodeKind::Comment(comment) => comment.doc(ctx, state),
             NodeKind::Doctype(doctype) => doctype.doc(ctx, state),
             NodeKind::Element(element) => element.doc(ctx, state),
             NodeKind::FrontMatter(front_matter) => front_matter.doc(ctx, state),
+            NodeKind::BladeBlock(blade_block) => blade_block.doc(ctx, state),
+            NodeKind::BladeComment(blade_comment) => blade_comment.doc(ctx, state),
+            NodeKind::BladeDirective(blade_directive) => blade_directive.doc(ctx, state),
+            NodeKind::BladeEcho(blade_echo) => blade_echo.doc(ctx, state),
+            NodeKind::ErbBlock(erb_block) => erb_block.doc(ctx, state),
+            NodeKind::ErbComment(erb_comment) => erb_comment.doc(ctx, state),
+            NodeKind::ErbTag(erb_tag) => erb_tag.doc(ctx, state),
+            NodeKind::GoTemplateBlock(go_template_block) => go_template_block.doc(ctx, state),
+            NodeKind::GoTemplateComment(go_template_comment) => go_template_comment.doc(ctx, state),
+            NodeKind::GoTemplateInterpolation(go_template_interpolation) => {
+                go_template_interpolation.doc(ctx, state)
+            }
+            NodeKind::HandlebarsBlock(handlebars_block) => handlebars_block.doc(ctx, state),
+            NodeKind::HandlebarsComment(handlebars_comment) => handlebars_comment.doc(ctx, state),
+            NodeKind::HandlebarsInterpolation(handlebars_interpolation) => {
+                handlebars_interpolation.doc(ctx, state)
+            }
+            NodeKind::HandlebarsTag(handlebars_tag) => handlebars_tag.doc(ctx, state),
             NodeKind::JinjaBlock(jinja_block) => jinja_block.doc(ctx, state),
             NodeKind::JinjaComment(jinja_comment) => jinja_comment.doc(ctx, state),
             NodeKind::JinjaInterpolation(jinja_interpolation) => {
                 jinja_interpolation.doc(ctx, state)
             }
             NodeKind::JinjaTag(jinja_tag) => jinja_tag.doc(ctx, state),
+            NodeKind::PhpBlock(php_block) => php_block.doc(ctx, state),
+            NodeKind::PhpTag(php_tag) => php_tag.doc(ctx, state),
+            NodeKind::ProcessingInstruction(processing_instruction) => {
+                processing_instruction.doc(ctx, state)
+            }
+            NodeKind::RazorCodeBlock(razor_code_block) => razor_code_block.doc(ctx, state),
+            NodeKind::RazorComment(razor_comment) => razor_comment.doc(ctx, state),
+            NodeKind::RazorControlFlow(razor_control_flow) => razor_control_flow.doc(ctx, state),
+            NodeKind::RazorDirective(razor_directive) => razor_directive.doc(ctx, state),
+            NodeKind::RazorExpr(razor_expr) => razor_expr.doc(ctx, state),
+            NodeKind::RazorStatement(razor_statement) => razor_statement.doc(ctx, state),
             NodeKind::SvelteAtTag(svelte_at_tag) => svelte_at_tag.doc(ctx, state),
             NodeKind::SvelteAwaitBlock(svelte_await_block) => svelte_await_block.doc(ctx, state),
             NodeKind::SvelteEachBlock(svelte_each_block) => svelte_each_block.doc(ctx, state),
@@ -1001,28 +1605,233 @@ impl<'s> DocGen<'s> for NodeKind<'s> {
     }
 }
 
-impl<'s> DocGen<'s> for Root<'s> {
+impl<'s> DocGen<'s> for PhpBlock<'s> {
     fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, state: &State<'s>) -> Doc<'s>
     where
         F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
     {
-        let is_whole_document_like = self.children.iter().any(|child| match &child.kind {
-            NodeKind::Doctype(..) => true,
-            NodeKind::Element(element) => element.tag_name.eq_ignore_ascii_case("html"),
-            _ => false,
-        });
-        let is_whitespace_sensitive = matches!(
-            ctx.options.whitespace_sensitivity,
-            WhitespaceSensitivity::Css | WhitespaceSensitivity::Strict
-        );
-        let has_two_more_non_text_children = has_two_more_non_text_children(&self.children);
-
-        if is_whole_document_like
-            && !matches!(
-                ctx.options.whitespace_sensitivity,
-                WhitespaceSensitivity::Strict
-            )
-            || !is_whitespace_sensitive && has_two_more_non_text_children
+        Doc::list(
+            self.body
+                .iter()
+                .map(|child| match child {
+                    PhpTagOrChildren::Tag(tag) => tag.doc(ctx, state),
+                    PhpTagOrChildren::Children(children) => {
+                        format_control_structure_block_children(children, ctx, state)
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<'s> DocGen<'s> for PhpTag<'s> {
+    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        let open = Doc::text(if self.echo { "<?=" } else { "<?php" });
+        if self.code.trim().is_empty() {
+            return if self.closed {
+                open.append(Doc::text(" ?>"))
+            } else {
+                open
+            };
+        }
+
+        // Code of block tags like `<?php if ($a): ?>` is incomplete, so it can't be formatted.
+        let code = if self.echo {
+            let expr = self.code.trim().trim_end_matches(';').trim_end();
+            let formatted = ctx.format_php_expr(expr, self.start);
+            if self.code.trim_end().ends_with(';') {
+                formatted + ";"
+            } else {
+                formatted
+            }
+        } else if let helpers::TemplateTagKind::Other = helpers::get_php_tag_kind(self.code) {
+            ctx.format_php(self.code, self.start)
+        } else {
+            self.code.trim().to_owned()
+        };
+        if !self.closed {
+            // PHP files usually omit `?>` at the end of file
+            open.append(Doc::hard_line())
+                .concat(reflow_with_indent(&code))
+        } else if code.contains('\n') {
+            // multi-line code is aligned with `<?php`, not indented
+            open.append(Doc::hard_line())
+                .concat(reflow_with_indent(&code))
+                .append(Doc::hard_line())
+                .append(Doc::text("?>"))
+        } else {
+            open.append(Doc::space())
+                .append(Doc::text(code))
+                .append(Doc::text(" ?>"))
+        }
+    }
+}
+
+impl<'s> DocGen<'s> for ProcessingInstruction<'s> {
+    fn doc<E, F>(&self, _: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        Doc::text("<?")
+            .concat(reflow_raw(self.raw))
+            .append(Doc::text("?>"))
+    }
+}
+
+impl<'s> DocGen<'s> for RazorCodeBlock<'s> {
+    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, state: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        let mut statements = self.children.iter().filter(|child| {
+            !matches!(&child.kind, NodeKind::Text(text_node) if is_all_ascii_whitespace(text_node.raw))
+        });
+        let first = statements.next();
+        let is_csharp_only = first.is_some()
+            && self.children.iter().all(|child| match &child.kind {
+                NodeKind::Text(text_node) => is_all_ascii_whitespace(text_node.raw),
+                NodeKind::RazorStatement(statement) => !statement.code.starts_with("@:"),
+                _ => false,
+            });
+        if let (
+            true,
+            Some(Node {
+                kind: NodeKind::RazorStatement(first),
+                ..
+            }),
+        ) = (is_csharp_only, first)
+        {
+            let end = match statements.next_back().map(|last| &last.kind) {
+                Some(NodeKind::RazorStatement(last)) => last.start + last.code.len(),
+                _ => first.start + first.code.len(),
+            };
+            let code = ctx.source.get(first.start..end).unwrap_or(first.code);
+            let formatted = ctx.format_script(code, "cs", first.start);
+            Doc::text("@{")
+                .append(
+                    Doc::hard_line()
+                        .concat(reflow_with_indent(formatted.trim()))
+                        .nest_with_ctx(ctx),
+                )
+                .append(Doc::hard_line())
+                .append(Doc::text("}"))
+        } else {
+            Doc::text("@{")
+                .append(format_razor_code_children(&self.children, ctx, state))
+                .append(Doc::text("}"))
+        }
+    }
+}
+
+impl<'s> DocGen<'s> for RazorComment<'s> {
+    fn doc<E, F>(&self, _: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        Doc::text("@*")
+            .concat(reflow_raw(self.raw))
+            .append(Doc::text("*@"))
+    }
+}
+
+impl<'s> DocGen<'s> for RazorControlFlow<'s> {
+    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, state: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        let mut doc = Doc::text(if self.at_sign { "@" } else { "" });
+        for (i, branch) in self.branches.iter().enumerate() {
+            if i > 0 {
+                doc = doc.append(Doc::space());
+            }
+            doc = doc
+                .concat(reflow_with_indent(branch.header))
+                .append(Doc::text(if branch.header.is_empty() { "{" } else { " {" }))
+                .append(format_razor_code_children(&branch.children, ctx, state))
+                .append(Doc::text("}"));
+        }
+        if let Some(trailer) = self.trailer {
+            doc = doc.append(Doc::space()).append(Doc::text(trailer));
+        }
+        doc
+    }
+}
+
+impl<'s> DocGen<'s> for RazorDirective<'s> {
+    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        let mut doc = Doc::text("@").append(Doc::text(self.name));
+        if !self.value.is_empty() {
+            doc = doc.append(Doc::space()).append(Doc::text(self.value));
+        }
+        match self.body {
+            // members of `@code { }` aren't complete C# code, so they're kept as is
+            Some((body, _)) if !body.trim().is_empty() => doc
+                .append(Doc::text(" {"))
+                .append(
+                    Doc::hard_line()
+                        .concat(reflow_with_indent(body.trim()))
+                        .nest_with_ctx(ctx),
+                )
+                .append(Doc::hard_line())
+                .append(Doc::text("}")),
+            Some(..) => doc.append(Doc::text(" {}")),
+            None => doc,
+        }
+    }
+}
+
+impl<'s> DocGen<'s> for RazorExpr<'s> {
+    fn doc<E, F>(&self, _: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        if self.explicit {
+            Doc::text("@(")
+                .concat(reflow_with_indent(self.expr.trim()))
+                .append(Doc::text(")"))
+        } else {
+            Doc::text("@").append(Doc::text(self.expr))
+        }
+    }
+}
+
+impl<'s> DocGen<'s> for RazorStatement<'s> {
+    fn doc<E, F>(&self, _: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        Doc::list(reflow_with_indent(self.code).collect())
+    }
+}
+
+impl<'s> DocGen<'s> for Root<'s> {
+    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, state: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        let is_whole_document_like = self.children.iter().any(|child| match &child.kind {
+            NodeKind::Doctype(..) => true,
+            NodeKind::Element(element) => element.tag_name.eq_ignore_ascii_case("html"),
+            _ => false,
+        });
+        let is_whitespace_sensitive = matches!(
+            ctx.options.whitespace_sensitivity,
+            WhitespaceSensitivity::Css | WhitespaceSensitivity::Strict
+        );
+        let has_two_more_non_text_children = has_two_more_non_text_children(&self.children);
+
+        if is_whole_document_like
+            && !matches!(
+                ctx.options.whitespace_sensitivity,
+                WhitespaceSensitivity::Strict
+            )
+            || !is_whitespace_sensitive && has_two_more_non_text_children
         {
             format_children_with_inserting_linebreak(&self.children, ctx, state)
                 .append(Doc::hard_line())
@@ -1043,10 +1852,40 @@ impl<'s> DocGen<'s> for SvelteAtTag<'s> {
     where
         F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
     {
+        let (code, start) = self.expr;
+        let formatted = if self.name == "const" {
+            let eq = helpers::find_top_level(code, |i, c| {
+                c == '='
+                    && !code[i + 1..].starts_with(['=', '>'])
+                    && !code[..i].ends_with(['=', '!', '<', '>'])
+            });
+            if let Some(eq) = eq {
+                let binding = ctx.format_binding(&code[..eq], start);
+                let init_start = start + eq + 1;
+                let init = ctx.format_expr(&code[eq + 1..], false, init_start);
+                format!("{binding} = {init}")
+            } else {
+                ctx.format_expr(code, false, start)
+            }
+        } else {
+            ctx.format_expr(code, false, start)
+        };
         Doc::text("{@")
             .append(Doc::text(self.name))
             .append(Doc::space())
-            .append(Doc::text(ctx.format_expr(self.expr.0, false, self.expr.1)))
+            .append(Doc::text(formatted))
+            .append(Doc::text("}"))
+    }
+}
+
+impl<'s> DocGen<'s> for SvelteAttachment<'s> {
+    fn doc<E, F>(&self, ctx: &mut Ctx<'s, E, F>, _: &State<'s>) -> Doc<'s>
+    where
+        F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+    {
+        let expr_code = ctx.format_expr(self.expr.0, false, self.expr.1);
+        Doc::text("{@attach ")
+            .concat(reflow_with_indent(&expr_code))
             .append(Doc::text("}"))
     }
 }
@@ -1367,6 +2206,36 @@ impl<'s> DocGen<'s> for VentoBlock<'s> {
     where
         F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
     {
+        if let [VentoTagOrChildren::Tag(first_tag), .., VentoTagOrChildren::Tag(last_tag)] =
+            &self.body[..]
+        {
+            if helpers::is_vento_raw_block(first_tag.tag, &ctx.options.vento_custom_tags) {
+                // Width of raw text can't be measured correctly for deciding line breaks,
+                // so tags of raw block are printed in one line.
+                let format_tag = |tag: &VentoTag| {
+                    Doc::text(format!(
+                        "{{{{{} {} {}}}}}",
+                        if tag.trim_prev { "-" } else { "" },
+                        tag.tag.trim(),
+                        if tag.trim_next { "-" } else { "" },
+                    ))
+                };
+                let mut docs = Vec::with_capacity(3);
+                docs.push(format_tag(first_tag));
+                if let Some(VentoTagOrChildren::Children(children)) = self.body.get(1) {
+                    if let [Node {
+                        kind: NodeKind::Text(text_node),
+                        ..
+                    }] = &children[..]
+                    {
+                        docs.extend(reflow_raw(text_node.raw));
+                    }
+                }
+                docs.push(format_tag(last_tag));
+                return Doc::list(docs);
+            }
+        }
+
         Doc::list(
             self.body
                 .iter()
@@ -1504,9 +2373,8 @@ impl<'s> DocGen<'s> for VentoTag<'s> {
                                 reflow_with_indent(&ctx.format_expr(parsed_tag.1, false, 0)),
                             )
                         }
-                    } else if parsed_tag.0 == "function" || parsed_tag.1.starts_with("function") {
-                        // unsupported at present
-                        Doc::list(reflow_with_indent(item.trim()).collect())
+                    } else if let Some(doc) = format_vento_function_header(item, ctx) {
+                        doc
                     } else if let (tag_name @ ("set" | "export"), rest) = parsed_tag {
                         if let Some((binding, expr)) = rest.trim().split_once('=') {
                             Doc::text(tag_name.to_string())
@@ -1653,9 +2521,9 @@ impl<'s> DocGen<'s> for VueDirective<'s> {
 
         if let Some((value, value_start)) = self.value {
             let value = match self.name {
-                "for" => {
+                "for" => ctx.with_escaping_quotes(value, |code, ctx| {
                     use crate::config::VForDelimiterStyle;
-                    if let Some((left, right)) = value.split_once(" in ") {
+                    if let Some((left, right)) = code.split_once(" in ") {
                         let delimiter = if let Some(VForDelimiterStyle::Of) =
                             ctx.options.v_for_delimiter_style
                         {
@@ -1664,7 +2532,7 @@ impl<'s> DocGen<'s> for VueDirective<'s> {
                             "in"
                         };
                         format_v_for(left, delimiter, right, value_start, ctx)
-                    } else if let Some((left, right)) = value.split_once(" of ") {
+                    } else if let Some((left, right)) = code.split_once(" of ") {
                         let delimiter = if let Some(VForDelimiterStyle::In) =
                             ctx.options.v_for_delimiter_style
                         {
@@ -1674,12 +2542,20 @@ impl<'s> DocGen<'s> for VueDirective<'s> {
                         };
                         format_v_for(left, delimiter, right, value_start, ctx)
                     } else {
-                        ctx.with_escaping_quotes(value, |code, ctx| {
-                            ctx.format_expr(&code, true, value_start)
-                        })
+                        ctx.format_expr(&code, true, value_start)
                     }
-                }
-                "#" | "slot" => ctx.format_binding(value, value_start),
+                }),
+                "#" | "slot" => ctx.with_escaping_quotes(value, |code, ctx| {
+                    ctx.format_binding(&code, value_start)
+                }),
+                "@" | "on" => ctx.with_escaping_quotes(value, |code, ctx| {
+                    if self.arg_and_modifiers.is_some() {
+                        format_v_on_value(&code, value_start, ctx)
+                    } else {
+                        // object syntax like `v-on="{ mousedown: doThis }"`
+                        ctx.format_expr(&code, true, value_start)
+                    }
+                }),
                 _ => {
                     if value.trim().trim_end_matches(';').contains(';')
                         && !helpers::UNESCAPING_AC.is_match(value)
@@ -2064,7 +2940,14 @@ fn is_text_like(node: &Node) -> bool {
         | NodeKind::SvelteInterpolation(..)
         | NodeKind::AstroExpr(..)
         | NodeKind::JinjaInterpolation(..)
-        | NodeKind::VentoInterpolation(..) => true,
+        | NodeKind::VentoInterpolation(..)
+        | NodeKind::HandlebarsInterpolation(..)
+        | NodeKind::GoTemplateInterpolation(..)
+        | NodeKind::BladeEcho(..)
+        | NodeKind::RazorExpr(..)
+        | NodeKind::Cdata(..) => true,
+        NodeKind::ErbTag(erb_tag) => erb_tag.prefix.starts_with('='),
+        NodeKind::PhpTag(php_tag) => php_tag.echo,
         NodeKind::Element(element) => element.tag_name.eq_ignore_ascii_case("label"),
         _ => false,
     }
@@ -2227,6 +3110,247 @@ fn format_ws_insensitive_trailing_ws<'s>(children: &[Node<'s>]) -> Doc<'s> {
         _ => Doc::line_or_nil(),
     }
 }
+
+fn format_v_on_value<'s, E, F>(code: &str, start: usize, ctx: &mut Ctx<'s, E, F>) -> String
+where
+    F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+{
+    // Like Vue compiler, method references and function expressions are
+    // treated as expressions, while others are inline statements.
+    // Object literal can't be statement, so it's treated as expression as well.
+    if is_vue_member_expr(code) || is_vue_fn_expr(code) || code.trim_start().starts_with('{') {
+        ctx.format_expr(code, true, start)
+    } else {
+        let errors_count = ctx.external_formatter_errors.len();
+        let formatted = ctx.format_fn_body(code, true, start);
+        if ctx.external_formatter_errors.len() > errors_count {
+            // It may be an expression that can't be used as statement.
+            ctx.external_formatter_errors.truncate(errors_count);
+            ctx.format_expr(code, true, start)
+        } else if formatted.contains('\n') {
+            formatted
+        } else {
+            formatted.trim_end_matches(';').trim_end().to_owned()
+        }
+    }
+}
+
+/// Format value of Alpine.js directive by reusing Vue directive logic,
+/// since their syntax is almost the same.
+fn format_alpine_attr_value<'s, E, F>(
+    name: &str,
+    value: &str,
+    start: usize,
+    ctx: &mut Ctx<'s, E, F>,
+) -> Option<String>
+where
+    F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+{
+    let directive = name.split('.').next().unwrap_or(name);
+    if directive.starts_with('@')
+        || directive.starts_with("x-on:")
+        || matches!(directive, "x-init" | "x-effect")
+    {
+        Some(ctx.with_escaping_quotes(value, |code, ctx| format_v_on_value(&code, start, ctx)))
+    } else if directive == "x-for" {
+        Some(ctx.with_escaping_quotes(value, |code, ctx| {
+            if let Some((left, right)) = code.split_once(" in ") {
+                format_v_for(left, "in", right, start, ctx)
+            } else {
+                ctx.format_expr(&code, true, start)
+            }
+        }))
+    } else if directive.starts_with(':')
+        || directive.starts_with("x-bind")
+        || matches!(
+            directive,
+            "x-data" | "x-show" | "x-text" | "x-html" | "x-model" | "x-modelable" | "x-if" | "x-id"
+        )
+    {
+        Some(ctx.with_escaping_quotes(value, |code, ctx| ctx.format_expr(&code, true, start)))
+    } else {
+        None
+    }
+}
+
+/// Format value of htmx attribute, which may be JSON, JavaScript or trigger list.
+fn format_htmx_attr_value<'s, E, F>(
+    name: &str,
+    value: &str,
+    start: usize,
+    ctx: &mut Ctx<'s, E, F>,
+) -> Option<String>
+where
+    F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+{
+    // htmx accepts attributes with `data-` prefix as well.
+    let name = name.strip_prefix("data-").unwrap_or(name);
+    match name {
+        "hx-vals" | "hx-headers" => Some(ctx.with_escaping_quotes(value, |code, ctx| {
+            let code = code.trim();
+            if let Some((prefix, expr)) = ["javascript:", "js:"]
+                .into_iter()
+                .find_map(|prefix| code.strip_prefix(prefix).map(|expr| (prefix, expr)))
+            {
+                format!("{prefix}{}", ctx.format_expr(expr, true, start))
+            } else {
+                ctx.format_json(code, start).trim().to_owned()
+            }
+        })),
+        "hx-trigger" => Some(ctx.with_escaping_quotes(value, |code, _| format_htmx_trigger(&code))),
+        _ if name.starts_with("hx-on:") || name.starts_with("hx-on-") => {
+            Some(ctx.with_escaping_quotes(value, |code, ctx| format_v_on_value(&code, start, ctx)))
+        }
+        _ => None,
+    }
+}
+
+/// Normalize whitespaces and commas of htmx trigger list,
+/// while keeping event filters and parenthesized selectors as-is.
+fn format_htmx_trigger(value: &str) -> String {
+    let mut triggers = vec![];
+    let mut current = String::with_capacity(value.len());
+    let mut depth = 0usize;
+    let mut pending_space = false;
+    for c in value.chars() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                triggers.push(mem::take(&mut current));
+                pending_space = false;
+                continue;
+            }
+            c if depth == 0 && c.is_ascii_whitespace() => {
+                pending_space = !current.is_empty();
+                continue;
+            }
+            _ => {}
+        }
+        if pending_space {
+            current.push(' ');
+            pending_space = false;
+        }
+        current.push(c);
+    }
+    triggers.push(current);
+    triggers
+        .iter()
+        .filter(|trigger| !trigger.is_empty())
+        .join(", ")
+}
+
+/// Format value of Aurelia binding command like `value.bind` or `repeat.for`.
+fn format_aurelia_attr_value<'s, E, F>(
+    name: &str,
+    value: &str,
+    start: usize,
+    ctx: &mut Ctx<'s, E, F>,
+) -> Option<String>
+where
+    F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+{
+    let (_, command) = name.rsplit_once('.')?;
+    match command {
+        "for" if name == "repeat.for" => Some(ctx.with_escaping_quotes(value, |code, ctx| {
+            let (expr, tail) = split_aurelia_expr(&code);
+            let formatted = if let Some((left, right)) = expr.split_once(" of ") {
+                format_v_for(left, "of", right, start, ctx)
+            } else {
+                ctx.format_expr(expr, true, start)
+            };
+            append_aurelia_expr_tail(formatted, tail)
+        })),
+        "bind" | "one-time" | "to-view" | "from-view" | "two-way" | "one-way" | "trigger"
+        | "delegate" | "capture" | "call" => Some(ctx.with_escaping_quotes(value, |code, ctx| {
+            let (expr, tail) = split_aurelia_expr(&code);
+            let formatted = ctx.format_expr(expr, true, start);
+            append_aurelia_expr_tail(formatted, tail)
+        })),
+        _ => None,
+    }
+}
+
+/// Split Aurelia binding expression into JavaScript expression and
+/// the rest that contains value converters (`| name`) and binding behaviors (`& name`),
+/// since they aren't valid JavaScript.
+fn split_aurelia_expr(code: &str) -> (&str, &str) {
+    let mut quote = None;
+    let mut escaped = false;
+    let mut depth = 0usize;
+    let mut chars = code.char_indices().peekable();
+    let mut prev = None;
+    while let Some((i, c)) = chars.next() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+        } else {
+            match c {
+                '\'' | '"' | '`' => quote = Some(c),
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth = depth.saturating_sub(1),
+                '|' | '&'
+                    if depth == 0
+                        && prev != Some(c)
+                        && chars
+                            .peek()
+                            .is_none_or(|(_, next)| *next != c && *next != '=') =>
+                {
+                    return (&code[..i], &code[i..]);
+                }
+                _ => {}
+            }
+        }
+        prev = Some(c);
+    }
+    (code, "")
+}
+
+fn append_aurelia_expr_tail(mut formatted: String, mut tail: &str) -> String {
+    while let Some(op) = tail.chars().next() {
+        let (segment, rest) = split_aurelia_expr(&tail[1..]);
+        formatted.push(' ');
+        formatted.push(op);
+        formatted.push(' ');
+        push_collapsed_whitespace(&mut formatted, segment);
+        tail = rest;
+    }
+    formatted
+}
+
+/// Collapse whitespaces outside quotes into single space and trim them,
+/// so string arguments like `dateFormat:'MMM  d'` are kept as is.
+fn push_collapsed_whitespace(formatted: &mut String, code: &str) {
+    let mut quote = None;
+    let mut escaped = false;
+    let mut has_space = false;
+    for c in code.trim().chars() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+        } else if c.is_ascii_whitespace() {
+            has_space = true;
+            continue;
+        } else if matches!(c, '\'' | '"' | '`') {
+            quote = Some(c);
+        }
+        if mem::take(&mut has_space) {
+            formatted.push(' ');
+        }
+        formatted.push(c);
+    }
+}
+
 fn format_v_for<'s, E, F>(
     left: &str,
     delimiter: &'static str,
@@ -2237,12 +3361,342 @@ fn format_v_for<'s, E, F>(
 where
     F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
 {
-    let left = ctx.format_expr(left, false, start);
+    let left = left.trim();
+    let (params, has_parens) = left
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .map(|params| (params, true))
+        .unwrap_or((left, false));
+
+    // Format all aliases as an array pattern at once,
+    // so destructuring and default values are formatted as real bindings
+    // and external formatter can decide whether they should be wrapped or not.
+    let formatted = ctx.format_binding(&format!("[{}]", params.trim()), start);
+    let formatted = formatted
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .unwrap_or(&formatted);
+    let formatted = formatted.trim_end();
+    // Trailing comma is added by external formatter when wrapping,
+    // but it isn't allowed by Vue.
+    let formatted = if formatted.contains('\n') {
+        formatted.strip_suffix(',').unwrap_or(formatted)
+    } else {
+        formatted
+    };
+
     let right = ctx.format_expr(right, false, start + 4);
-    if left.contains(',') && !left.contains('(') {
-        format!("({left}) {delimiter} {right}")
+    if has_parens || helpers::has_top_level_comma(formatted) {
+        if formatted.starts_with('\n') {
+            format!("({formatted}\n) {delimiter} {right}")
+        } else {
+            format!("({}) {delimiter} {right}", formatted.trim_start())
+        }
+    } else {
+        format!("{} {delimiter} {right}", formatted.trim_start())
+    }
+}
+
+/// Check if code is a method reference like `handler`, `a.b` or `a['b']`,
+/// which should be kept as expression by Vue.
+fn is_vue_member_expr(code: &str) -> bool {
+    fn is_ident_start(c: char) -> bool {
+        c.is_alphabetic() || c == '_' || c == '$' || !c.is_ascii()
+    }
+    fn is_ident_char(c: char) -> bool {
+        c.is_alphanumeric() || c == '_' || c == '$' || !c.is_ascii()
+    }
+
+    let mut chars = code.trim().chars().peekable();
+    if chars.next_if(|c| is_ident_start(*c)).is_none() {
+        return false;
+    }
+    loop {
+        while chars.next_if(|c| is_ident_char(*c)).is_some() {}
+        while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+        match chars.next() {
+            Some('.') => {}
+            Some('?') if chars.next_if(|c| *c == '.').is_some() => {
+                if chars.peek() == Some(&'[') {
+                    continue;
+                }
+            }
+            Some('[') => {
+                let mut pair_stack = vec!['['];
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\'' | '"' | '`' => {
+                            if pair_stack.last().is_some_and(|last| *last == c) {
+                                pair_stack.pop();
+                            } else if !matches!(pair_stack.last(), Some('\'' | '"' | '`')) {
+                                pair_stack.push(c);
+                            }
+                        }
+                        '\\' if matches!(pair_stack.last(), Some('\'' | '"' | '`')) => {
+                            chars.next();
+                        }
+                        '[' if !matches!(pair_stack.last(), Some('\'' | '"' | '`')) => {
+                            pair_stack.push(c);
+                        }
+                        ']' if matches!(pair_stack.last(), Some('[')) => {
+                            pair_stack.pop();
+                            if pair_stack.is_empty() {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                if !pair_stack.is_empty() {
+                    return false;
+                }
+                continue;
+            }
+            None => return true,
+            _ => return false,
+        }
+        while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+        if chars.next_if(|c| is_ident_start(*c)).is_none() {
+            return false;
+        }
+    }
+}
+
+/// Check if code is an arrow function or a function expression,
+/// which should be kept as expression by Vue.
+fn is_vue_fn_expr(code: &str) -> bool {
+    let code = code.trim_start();
+    let code = code
+        .strip_prefix("async")
+        .filter(|rest| rest.starts_with(|c: char| c.is_ascii_whitespace() || c == '('))
+        .map(|rest| rest.trim_start())
+        .unwrap_or(code);
+    if code
+        .strip_prefix("function")
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_whitespace() || c == '('))
+    {
+        return true;
+    }
+
+    let rest = if let Some(rest) = code.strip_prefix('(') {
+        let Some(index) = rest.find(')') else {
+            return false;
+        };
+        &rest[index + 1..]
     } else {
-        format!("{left} {delimiter} {right}")
+        let ident_len = code
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+            .unwrap_or(code.len());
+        if ident_len == 0 {
+            return false;
+        }
+        &code[ident_len..]
+    };
+    let rest = rest.trim_start();
+    if let Some(return_type) = rest.strip_prefix(':') {
+        return_type
+            .find('=')
+            .is_some_and(|index| return_type[index..].starts_with("=>"))
+    } else {
+        rest.starts_with("=>")
+    }
+}
+
+fn format_go_template_action<'s>(action: &str, trim_prev: bool, trim_next: bool) -> Doc<'s> {
+    let mut docs = Vec::with_capacity(5);
+    docs.push(Doc::text(if trim_prev { "{{- " } else { "{{ " }));
+    docs.push(Doc::text(format_go_template_expr(action)));
+    docs.push(Doc::text(if trim_next { " -}}" } else { " }}" }));
+    Doc::list(docs)
+}
+
+/// Normalize whitespaces in Go template action outside of string literals,
+/// so pipelines are written like `.Title | upper` and declarations like `$x := .`.
+fn format_go_template_expr(expr: &str) -> String {
+    let mut formatted = String::with_capacity(expr.len());
+    let mut chars = expr.trim().chars().peekable();
+    let mut pending_ws = false;
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_ascii_whitespace() => pending_ws = true,
+            '|' | '=' | ':' if c != ':' || chars.peek() == Some(&'=') => {
+                formatted.truncate(formatted.trim_end().len());
+                formatted.push(' ');
+                formatted.push(c);
+                if c == ':' {
+                    chars.next();
+                    formatted.push('=');
+                }
+                pending_ws = true;
+            }
+            ',' | ')' => {
+                formatted.push(c);
+                pending_ws = c == ',';
+            }
+            _ => {
+                if pending_ws && !formatted.is_empty() && !formatted.ends_with('(') {
+                    formatted.push(' ');
+                }
+                pending_ws = false;
+                formatted.push(c);
+                if matches!(c, '"' | '\'' | '`') {
+                    while let Some(next) = chars.next() {
+                        formatted.push(next);
+                        if next == '\\' && c != '`' {
+                            formatted.extend(chars.next());
+                        } else if next == c {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    formatted
+}
+
+/// Normalize whitespaces in Handlebars expression outside of string literals,
+/// such as `helper  arg ( sub  a )` and `as | item |`.
+fn format_handlebars_expr(expr: &str) -> String {
+    let mut formatted = String::with_capacity(expr.len());
+    let mut quote = None;
+    let mut escaped = false;
+    let mut in_block_params = false;
+    let mut pending_ws = false;
+    for c in expr.trim().chars() {
+        if let Some(q) = quote {
+            formatted.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        if c.is_ascii_whitespace() {
+            pending_ws = true;
+            continue;
+        }
+        let is_closing = c == ')' || c == '|' && in_block_params;
+        if pending_ws
+            && !is_closing
+            && c != '='
+            && !formatted.ends_with(['(', '='])
+            && !(in_block_params && formatted.ends_with('|'))
+        {
+            formatted.push(' ');
+        }
+        pending_ws = false;
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '|' => in_block_params = !in_block_params,
+            _ => {}
+        }
+        formatted.push(c);
+    }
+    formatted
+}
+
+/// Astro doesn't process scripts with `is:inline` (which is implied by `define:vars`),
+/// so they must be plain JavaScript.
+fn is_astro_inline_script(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| match attr {
+        Attribute::Native(native_attr) => native_attr.name == "is:inline",
+        Attribute::Astro(astro_attr) => astro_attr.name == Some("define:vars"),
+        _ => false,
+    })
+}
+
+/// Reorder Astro `client:*` directives into canonical order
+/// while keeping other attributes where they are.
+fn sort_astro_client_directives<'a, 's>(attrs: &'a [Attribute<'s>]) -> Vec<&'a Attribute<'s>> {
+    fn client_directive_rank(attr: &Attribute) -> Option<usize> {
+        let name = match attr {
+            Attribute::Native(native_attr) => native_attr.name,
+            Attribute::Astro(astro_attr) => astro_attr.name?,
+            _ => return None,
+        };
+        let directive = name.strip_prefix("client:")?;
+        Some(
+            ["load", "idle", "visible", "media", "only"]
+                .iter()
+                .position(|canonical| *canonical == directive)
+                .unwrap_or(usize::MAX),
+        )
+    }
+
+    let mut attrs = attrs.iter().collect::<Vec<_>>();
+    let slots = attrs
+        .iter()
+        .enumerate()
+        .filter_map(|(i, attr)| client_directive_rank(attr).map(|_| i))
+        .collect::<Vec<_>>();
+    let mut directives = slots.iter().map(|i| attrs[*i]).collect::<Vec<_>>();
+    directives.sort_by_key(|attr| client_directive_rank(attr));
+    slots
+        .into_iter()
+        .zip(directives)
+        .for_each(|(i, attr)| attrs[i] = attr);
+    attrs
+}
+
+/// Format Svelte attribute value that mixes literal text with `{expr}` interpolations,
+/// such as `class="card {active ? 'on' : ''}"`.
+/// Whitespaces in literal parts are normalized only for `class`, since they're significant elsewhere.
+/// Returns `None` if braces aren't balanced or formatted value can't be quoted safely.
+fn format_svelte_mixed_attr_value<'s, E, F>(
+    value: &'s str,
+    value_start: usize,
+    normalize_ws: bool,
+    ctx: &mut Ctx<'s, E, F>,
+) -> Option<String>
+where
+    F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+{
+    let push_literal = |literal: &str, formatted: &mut String| {
+        if !normalize_ws {
+            formatted.push_str(literal);
+            return;
+        }
+        let mut prev_is_ws = false;
+        literal.chars().for_each(|c| {
+            if !c.is_ascii_whitespace() {
+                formatted.push(c);
+            } else if !prev_is_ws {
+                formatted.push(' ');
+            }
+            prev_is_ws = c.is_ascii_whitespace();
+        });
+    };
+
+    // Leading and trailing whitespaces are part of attribute value, so they're kept as is.
+    let trimmed = value.trim_start_matches(|c: char| c.is_ascii_whitespace());
+    let leading_ws = &value[..value.len() - trimmed.len()];
+    let trimmed = trimmed.trim_end_matches(|c: char| c.is_ascii_whitespace());
+    let trailing_ws = &value[leading_ws.len() + trimmed.len()..];
+
+    let mut formatted = String::with_capacity(value.len());
+    formatted.push_str(leading_ws);
+    let mut rest = trimmed;
+    while let Some(brace) = rest.find('{') {
+        push_literal(&rest[..brace], &mut formatted);
+        let expr = &rest[brace + 1..];
+        let end = helpers::find_top_level(expr, |_, c| c == '}')?;
+        let expr_start = value_start + (value.len() - expr.len());
+        formatted.push('{');
+        formatted.push_str(&ctx.format_expr(&expr[..end], true, expr_start));
+        formatted.push('}');
+        rest = &expr[end + 1..];
+    }
+    push_literal(rest, &mut formatted);
+    formatted.push_str(trailing_ws);
+
+    if formatted.contains('"') && formatted.contains('\'') {
+        None
+    } else {
+        Some(formatted)
     }
 }
 
@@ -2271,6 +3725,398 @@ where
     }
 }
 
+/// Format children of Razor code block, putting each statement or markup node on its own line.
+fn format_razor_code_children<'s, E, F>(
+    children: &[Node<'s>],
+    ctx: &mut Ctx<'s, E, F>,
+    state: &State<'s>,
+) -> Doc<'s>
+where
+    F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+{
+    let mut docs = Vec::with_capacity(children.len() * 2);
+    // statements and markup after `case` or `default` label of `switch` are indented
+    let mut case_docs: Option<Vec<Doc<'s>>> = None;
+    let mut has_empty_line = false;
+    for child in children {
+        if let NodeKind::Text(text_node) = &child.kind {
+            if is_all_ascii_whitespace(text_node.raw) {
+                has_empty_line = text_node.line_breaks > 1;
+                continue;
+            }
+        }
+        let is_case_label = matches!(&child.kind, NodeKind::RazorStatement(statement)
+        if statement.code.ends_with(':')
+            && matches!(
+                statement.code.split(|c: char| !c.is_alphanumeric() && c != '_').next(),
+                Some("case" | "default")
+            ));
+        if is_case_label {
+            if let Some(case_docs) = case_docs.take().filter(|docs| !docs.is_empty()) {
+                docs.push(Doc::list(case_docs).nest_with_ctx(ctx));
+            }
+        }
+        let target = match &mut case_docs {
+            Some(case_docs) if !is_case_label => case_docs,
+            _ => &mut docs,
+        };
+        if has_empty_line && !target.is_empty() {
+            target.push(Doc::empty_line());
+        }
+        has_empty_line = false;
+        target.push(Doc::hard_line());
+        target.push(child.kind.doc(ctx, state));
+        if is_case_label {
+            case_docs = Some(vec![]);
+        }
+    }
+    if let Some(case_docs) = case_docs.filter(|docs| !docs.is_empty()) {
+        docs.push(Doc::list(case_docs).nest_with_ctx(ctx));
+    }
+    if docs.is_empty() {
+        Doc::nil()
+    } else {
+        Doc::list(docs).nest_with_ctx(ctx).append(Doc::hard_line())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum EmbeddedCode {
+    Script,
+    Json,
+    Style,
+}
+
+/// Format code in `<script>` or `<style>` which may contain template tags,
+/// like `const user = {{ user | tojson }};` in Jinja.
+///
+/// Template tags are replaced with placeholders that are valid JavaScript, JSON or CSS
+/// before sending code to external formatter, then they're restored.
+fn format_with_template_tags<'s, E, F>(
+    code: &'s str,
+    kind: EmbeddedCode,
+    ctx: &mut Ctx<'s, E, F>,
+    format: impl FnOnce(&mut Ctx<'s, E, F>, &str) -> String,
+) -> Cow<'s, str>
+where
+    F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+{
+    let tags = find_template_tags(code, ctx);
+    if tags.is_empty() {
+        return Cow::from(format(ctx, code));
+    }
+
+    let mut replaced = String::with_capacity(code.len());
+    let mut placeholders = Vec::with_capacity(tags.len());
+    let mut last = 0;
+    let mut in_json_string = false;
+    for (i, (start, end, is_stmt)) in tags.into_iter().enumerate() {
+        let segment = &code[last..start];
+        replaced.push_str(segment);
+        if let EmbeddedCode::Json = kind {
+            let mut escaped = false;
+            segment.chars().for_each(|c| match c {
+                '\\' => escaped = !escaped,
+                '"' if !escaped => in_json_string = !in_json_string,
+                _ => escaped = false,
+            });
+        }
+        // Tags like `{% if %}` can't be used as expression, so use comments for them.
+        let placeholder = match (kind, is_stmt) {
+            (EmbeddedCode::Script | EmbeddedCode::Json, true) => format!("/*$MarkupTpl{i}$*/"),
+            (EmbeddedCode::Json, false) if !in_json_string => format!("\"$MarkupTpl{i}$\""),
+            (EmbeddedCode::Script | EmbeddedCode::Json, false) => format!("$MarkupTpl{i}$"),
+            // escaped `@@` in Razor, such as `@@media`, which must be kept as at-rule
+            (EmbeddedCode::Style, false) if &code[start..end] == "@@" => {
+                format!("@markup-tpl-{i}-")
+            }
+            (EmbeddedCode::Style, false) => format!("markup-tpl-{i}-"),
+            (EmbeddedCode::Style, true) => format!("/*markup-tpl-{i}-*/"),
+        };
+        replaced.push_str(&placeholder);
+        placeholders.push((placeholder, start, end, is_stmt));
+        last = end;
+    }
+    replaced.push_str(&code[last..]);
+
+    let mut formatted = format(ctx, &replaced);
+    let mut last = 0;
+    let mut restored = Vec::with_capacity(placeholders.len());
+    for (placeholder, start, end, is_stmt) in placeholders {
+        let Some(index) = formatted[last..]
+            .find(&placeholder)
+            .map(|index| last + index)
+        else {
+            // placeholder was dropped or moved by external formatter
+            return Cow::from(code);
+        };
+        formatted.replace_range(index..index + placeholder.len(), &code[start..end]);
+        last = index + end - start;
+        restored.push((start, end, index, last, is_stmt));
+    }
+
+    // Statement tags are replaced with comments, so external formatter may
+    // remove or move code around them, such as commas and braces.
+    // Characters next to them must be kept, except inserted semicolons.
+    let is_significant = |c: &char| !c.is_whitespace() && *c != ';';
+    if restored
+        .into_iter()
+        .any(|(start, end, index, last, is_stmt)| {
+            is_stmt
+                && (code[..start].chars().rev().find(is_significant)
+                    != formatted[..index].chars().rev().find(is_significant)
+                    || code[end..].chars().find(is_significant)
+                        != formatted[last..].chars().find(is_significant))
+        })
+    {
+        Cow::from(code)
+    } else {
+        Cow::from(formatted)
+    }
+}
+
+/// Format attribute value which contains template tags in Jinja or Vento,
+/// like `class="btn {% if active %}on{% endif %}"`.
+///
+/// Spaces inside tags are normalized and other parts are kept as is,
+/// except `class` attribute which can be broken into multiple lines.
+fn format_template_attr_value<'s, E, F>(
+    value: &'s str,
+    is_class: bool,
+    ctx: &mut Ctx<'s, E, F>,
+) -> Option<Doc<'s>>
+where
+    F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+{
+    if !matches!(ctx.language, Language::Jinja | Language::Vento) {
+        return None;
+    }
+    let tags = find_template_tags(value, ctx);
+    if tags.is_empty() {
+        return None;
+    }
+
+    // For `class` attribute, each word consists of literals and tags without whitespaces.
+    // Everything between `if`/`for` tag and its end tag is treated as a single word.
+    let mut words = vec![vec![]];
+    let mut depth = 0usize;
+    let push_literal = |literal: &'s str, words: &mut Vec<Vec<Doc<'s>>>, depth: usize| {
+        if is_class && depth > 0 {
+            if let (false, Some(word)) = (literal.is_empty(), words.last_mut()) {
+                let mut collapsed = literal.split_ascii_whitespace().join(" ");
+                if literal.starts_with(|c: char| c.is_ascii_whitespace()) {
+                    collapsed.insert(0, ' ');
+                }
+                if literal.ends_with(|c: char| c.is_ascii_whitespace()) && collapsed != " " {
+                    collapsed.push(' ');
+                }
+                word.push(Doc::text(collapsed));
+            }
+        } else if is_class {
+            literal
+                .split(|c: char| c.is_ascii_whitespace())
+                .enumerate()
+                .for_each(|(i, part)| {
+                    if i > 0 && words.last().is_some_and(|word| !word.is_empty()) {
+                        words.push(vec![]);
+                    }
+                    if let (false, Some(word)) = (part.is_empty(), words.last_mut()) {
+                        word.push(Doc::text(part));
+                    }
+                });
+        } else if let Some(word) = words.last_mut() {
+            word.extend(reflow_raw(literal));
+        }
+    };
+    let mut last = 0;
+    for (start, end, is_stmt) in tags {
+        push_literal(&value[last..start], &mut words, depth);
+        let tag = &value[start..end];
+        let (open, content, close) = (&tag[..2], &tag[2..tag.len() - 2], &tag[tag.len() - 2..]);
+        let doc = if content.starts_with('#') {
+            // Vento comment
+            Doc::text(tag)
+        } else {
+            let (prefix, content, suffix) = helpers::split_jinja_whitespace_control(content);
+            if is_stmt {
+                match content.split_ascii_whitespace().next() {
+                    Some("if" | "for") => depth += 1,
+                    Some("endif" | "endfor" | "/if" | "/for") => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+            }
+            Doc::text(format!("{open}{prefix} {} {suffix}{close}", content.trim()))
+        };
+        if let Some(word) = words.last_mut() {
+            word.push(doc);
+        }
+        last = end;
+    }
+    push_literal(&value[last..], &mut words, depth);
+
+    if is_class {
+        Some(
+            Doc::list(
+                itertools::intersperse(
+                    words
+                        .into_iter()
+                        .filter(|word| !word.is_empty())
+                        .map(Doc::list),
+                    Doc::line_or_space(),
+                )
+                .collect(),
+            )
+            .nest_with_ctx(ctx)
+            .group(),
+        )
+    } else {
+        Some(Doc::list(words.into_iter().flatten().collect()))
+    }
+}
+
+/// Find template tags in code of `<script>`, `<style>` or attribute value.
+/// PHP tags and Razor code are only recognized in `<script>` and `<style>`.
+/// Returns start, end and whether the tag is a statement for each tag.
+fn find_template_tags<E, F>(code: &str, ctx: &Ctx<'_, E, F>) -> Vec<(usize, usize, bool)>
+where
+    F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+{
+    match ctx.language {
+        Language::Jinja | Language::Vento => {}
+        Language::Php => return find_php_tags(code),
+        Language::Razor => return find_razor_code(code),
+        _ => return vec![],
+    }
+
+    let mut tags = vec![];
+    let mut pos = 0;
+    while let Some(offset) = code[pos..].find('{') {
+        let start = pos + offset;
+        let rest = &code[start..];
+        let tag = if rest.starts_with("{{") {
+            let mut braces_stack = 0usize;
+            let mut chars = rest.char_indices().skip(2).peekable();
+            let mut end = None;
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '{' => braces_stack += 1,
+                    '}' if braces_stack == 0 && chars.next_if(|(_, c)| *c == '}').is_some() => {
+                        end = Some(i + 2);
+                        break;
+                    }
+                    '}' => braces_stack = braces_stack.saturating_sub(1),
+                    _ => {}
+                }
+            }
+            end.map(|end| {
+                let is_stmt = if let Language::Vento = ctx.language {
+                    let content = rest[2..end - 2].trim_matches('-').trim();
+                    let (tag_name, _) = helpers::parse_vento_tag(content);
+                    content.starts_with(['#', '>', '/'])
+                        || !helpers::is_vento_interpolation(tag_name)
+                        || ctx.options.vento_custom_tags.contains_key(tag_name)
+                } else {
+                    false
+                };
+                (end, is_stmt)
+            })
+        } else if let (Language::Jinja, Some(close)) = (
+            &ctx.language,
+            if rest.starts_with("{%") {
+                Some("%}")
+            } else if rest.starts_with("{#") {
+                Some("#}")
+            } else {
+                None
+            },
+        ) {
+            rest[2..].find(close).map(|i| (i + 4, true))
+        } else {
+            None
+        };
+
+        if let Some((len, is_stmt)) = tag {
+            tags.push((start, start + len, is_stmt));
+            pos = start + len;
+        } else {
+            pos = start + 1;
+        }
+    }
+    tags
+}
+
+/// Find PHP tags like `<?= $value ?>` in code of `<script>` or `<style>`.
+fn find_php_tags(code: &str) -> Vec<(usize, usize, bool)> {
+    let mut tags = vec![];
+    let mut pos = 0;
+    while let Some(offset) = code[pos..].find("<?") {
+        let start = pos + offset;
+        if let Some((len, is_stmt)) = parser::php_tag_len(&code[start..]) {
+            tags.push((start, start + len, is_stmt));
+            pos = start + len;
+        } else {
+            pos = start + 1;
+        }
+    }
+    tags
+}
+
+/// Find Razor code like `@Model.Name`, `@(a + b)`, `@{ }` or escaped `@@` in code of `<script>` or `<style>`.
+fn find_razor_code(code: &str) -> Vec<(usize, usize, bool)> {
+    let mut tags = vec![];
+    let mut pos = 0;
+    while let Some(offset) = code[pos..].find('@') {
+        let start = pos + offset;
+        // `@` after word is literal text like email address, but `@@` is always escaped.
+        let is_escaped = code[start + 1..].starts_with('@');
+        if let Some((len, is_stmt)) = parser::razor_code_len(&code[start..])
+            .filter(|_| is_escaped || !code[..start].ends_with(|c: char| c.is_alphanumeric()))
+        {
+            tags.push((start, start + len, is_stmt));
+            pos = start + len;
+        } else {
+            pos = start + 1;
+        }
+    }
+    tags
+}
+
+/// Format Vento function tag like `{{ export async function name(args) }}`.
+/// Returns `None` if it isn't a function tag.
+fn format_vento_function_header<'s, E, F>(tag: &'s str, ctx: &mut Ctx<'s, E, F>) -> Option<Doc<'s>>
+where
+    F: for<'a> FnMut(&'a str, Hints) -> Result<Cow<'a, str>, E>,
+{
+    let mut keywords = vec![];
+    let mut rest = tag;
+    loop {
+        let (keyword, signature) = helpers::parse_vento_tag(rest);
+        match keyword {
+            "export" | "async" => {
+                keywords.push(keyword);
+                rest = signature;
+            }
+            "function" => {
+                keywords.push(keyword);
+                rest = signature;
+                break;
+            }
+            _ => return None,
+        }
+    }
+
+    let doc = Doc::text(keywords.join(" "));
+    Some(if rest.is_empty() {
+        doc
+    } else if rest.contains('(') {
+        doc.append(Doc::space())
+            .concat(reflow_with_indent(&ctx.format_function_signature(rest)))
+    } else {
+        // function without parameters, like `{{ function hello }}`
+        doc.append(Doc::space()).append(Doc::text(rest.trim()))
+    })
+}
+
 fn format_vento_stmt_header<'s, E, F>(
     tag_keyword: &'static str,
     fake_keyword: &'static str,
@@ -4,32 +4,35 @@ use std::{collections::HashMap, fs, path::Path};
 
 #[test]
 fn fmt_snapshot() {
-    glob!("fmt/**/*.{html,vue,svelte,astro,jinja,njk,vto}", |path| {
-        let input = fs::read_to_string(path).unwrap();
-        let language = detect_language(path).unwrap();
+    glob!(
+        "fmt/**/*.{html,vue,svelte,astro,jinja,njk,tera,askama,vto,hbs,mustache,liquid,gohtml,tmpl,erb,eex,heex,php,phtml,cshtml,razor,xml,svg,xhtml,plist,csproj,mjml}",
+        |path| {
+            let input = fs::read_to_string(path).unwrap();
+            let language = detect_language(path).unwrap();
 
-        let options = fs::read_to_string(path.with_file_name("config.toml"))
-            .map(|config_file| {
-                toml::from_str::<HashMap<String, FormatOptions>>(&config_file).unwrap()
-            })
-            .ok();
+            let options = fs::read_to_string(path.with_file_name("config.toml"))
+                .map(|config_file| {
+                    toml::from_str::<HashMap<String, FormatOptions>>(&config_file).unwrap()
+                })
+                .ok();
 
-        if let Some(options) = options {
-            options.into_iter().for_each(|(option_name, options)| {
-                let output = run_format_test(path, &input, &options, language.clone());
+            if let Some(options) = options {
+                options.into_iter().for_each(|(option_name, options)| {
+                    let output = run_format_test(path, &input, &options, language.clone());
+                    build_settings(path).bind(|| {
+                        let name = path.file_stem().unwrap().to_str().unwrap();
+                        assert_snapshot!(format!("{name}.{option_name}"), output);
+                    });
+                })
+            } else {
+                let output = run_format_test(path, &input, &Default::default(), language);
                 build_settings(path).bind(|| {
                     let name = path.file_stem().unwrap().to_str().unwrap();
-                    assert_snapshot!(format!("{name}.{option_name}"), output);
+                    assert_snapshot!(name, output);
                 });
-            })
-        } else {
-            let output = run_format_test(path, &input, &Default::default(), language);
-            build_settings(path).bind(|| {
-                let name = path.file_stem().unwrap().to_str().unwrap();
-                assert_snapshot!(name, output);
-            });
+            }
         }
-    });
+    );
 }
 
 fn run_format_test(
@@ -75,3 +78,24 @@ fn build_settings(path: &Path) -> Settings {
     settings.remove_info();
     settings
 }
+
+#[test]
+fn php_echo_tag_as_statement() {
+    let output = format_text(
+        "<p><?= htmlspecialchars( $item->name ) ?></p>\n<p><?= $count; ?></p>\n",
+        Language::Php,
+        &Default::default(),
+        |code, hints| {
+            // bare expression without `;` isn't valid PHP
+            if hints.ext == "php" && !code.trim_end().ends_with(';') {
+                return Err(code.to_owned());
+            }
+            Ok(code.replace("( ", "(").replace(" )", ")").into())
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        output,
+        "<p><?= htmlspecialchars($item->name) ?></p>\n<p><?= $count; ?></p>\n"
+    );
+}
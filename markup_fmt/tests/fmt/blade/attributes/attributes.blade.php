@@ -0,0 +1,4 @@
+<x-alert type="error" :message="$message" class="mt-4" />
+<input type="checkbox" @checked($active) @disabled($locked)>
+<button @click="open = !open" @if($primary) class="primary" @endif>Toggle</button>
+<div @class(['p-4', 'font-bold' => $active]) title="{{ $title }}"></div>
@@ -0,0 +1,42 @@
+@extends('layouts.app')
+@section('title', 'Page Title')
+@section('content')
+<ul>
+@foreach($users as $user)
+<li>{{ $user->name }}</li>
+@endforeach
+</ul>
+@if(count($records) === 1)
+<p>One</p>
+@elseif (count($records) > 1)
+<p>Many</p>
+@else
+<p>None</p>
+@endif
+@forelse ($users as $user)
+<li>{{ $user->name }}</li>
+@empty
+<p>No users</p>
+@endforelse
+@switch($i)
+@case(1)
+<span>First</span>
+@break
+@default
+<span>Default</span>
+@endswitch
+@auth('admin')
+<a href="/admin">Admin</a>
+@endauth
+@unless(Auth::check())
+You are not signed in. Contact admin@example.com or @someone.
+@endunless
+@include('partials.footer', ['year' => 2024])
+@csrf
+@endsection
+@push('scripts')
+<script src="/app.js"></script>
+@endpush
+@verbatim
+<div>{{ name }}   @if</div>
+@endverbatim
@@ -0,0 +1,3 @@
+<p>Hello, {{$name}}. {!!   $html   !!}</p>
+{{-- a comment --}}
+<p>@{{ escaped }} and @@if</p>
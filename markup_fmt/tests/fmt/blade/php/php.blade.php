@@ -0,0 +1,10 @@
+<div>
+@php($y = 0)
+@php
+$counter = 1;
+@endphp
+@php($x = 2)
+@php
+$counter++;
+@endphp
+</div>
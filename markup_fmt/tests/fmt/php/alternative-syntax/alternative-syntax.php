@@ -0,0 +1,24 @@
+<?php $title = 'Posts' ?>
+<ul>
+<?php foreach ($posts as $post): ?>
+<?php if ($post->published): ?>
+<li><a href="<?= $post->url ?>"><?= htmlspecialchars($post->title) ?></a></li>
+<?php elseif ($post->draft): ?>
+<li class="draft"><?= $post->title ?></li>
+<?php else: ?>
+<li>Hidden</li>
+<?php endif; ?>
+<?php endforeach; ?>
+</ul>
+<?php if ($user) { ?>
+<p>Hello, <?= $user->name ?>!</p>
+<?php } else { ?>
+<p>Please <a href="/login">log in</a>.</p>
+<?php } ?>
+<?php switch ($status): ?>
+<?php case 'ok': ?>
+<span>OK</span>
+<?php break; ?>
+<?php default: ?>
+<span>Error</span>
+<?php endswitch; ?>
@@ -0,0 +1,15 @@
+<?php
+require __DIR__ . '/bootstrap.php';
+$items = get_items(); // load items
+?>
+<!DOCTYPE html>
+<html>
+<body>
+<?php /* comment with ?> inside */ echo 'a'; ?>
+<?=
+$footer
+?>
+</body>
+</html>
+<?php
+render_footer();
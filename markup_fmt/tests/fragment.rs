@@ -0,0 +1,121 @@
+use markup_fmt::{format_fragment, FormatError, Language, Placeholders};
+use std::ops::Range;
+
+fn format_with_ranges(code: &str, ranges: &[Range<usize>]) -> Result<String, FormatError<()>> {
+    format_fragment(
+        code,
+        Placeholders::Ranges(ranges),
+        Language::Html,
+        &Default::default(),
+        |code, _| Ok(code.into()),
+    )
+}
+
+fn format_with_pattern(code: &str, pattern: &str) -> String {
+    format_fragment(
+        code,
+        Placeholders::Pattern(pattern),
+        Language::Html,
+        &Default::default(),
+        |code, _| Ok::<_, ()>(code.into()),
+    )
+    .unwrap()
+}
+
+/// Find ranges of all `${...}` in code.
+fn find_interpolations(code: &str) -> Vec<Range<usize>> {
+    code.match_indices("${")
+        .map(|(start, _)| start..start + code[start..].find('}').unwrap() + 1)
+        .collect()
+}
+
+#[test]
+fn placeholder_as_tag_name() {
+    assert_eq!(
+        format_with_pattern("<div><PH_0_  a=\"b\">x</PH_0_></div>", "PH_{}_"),
+        "<div><PH_0_ a=\"b\">x</PH_0_></div>\n",
+    );
+}
+
+#[test]
+fn placeholder_as_attr_name() {
+    let code = "<input   ${attrs}  type=\"text\">";
+    assert_eq!(
+        format_with_ranges(code, &find_interpolations(code)).unwrap(),
+        "<input ${attrs} type=\"text\">\n",
+    );
+}
+
+#[test]
+fn placeholder_as_unquoted_attr_value() {
+    let code = "<a href=${url}   target=_blank>link</a>";
+    assert_eq!(
+        format_with_ranges(code, &find_interpolations(code)).unwrap(),
+        "<a href=\"${url}\" target=\"_blank\">link</a>\n",
+    );
+}
+
+#[test]
+fn placeholder_in_quoted_attr_value() {
+    let code = "<div class=\"card ${cls}\"  title='${title}'></div>";
+    assert_eq!(
+        format_with_ranges(code, &find_interpolations(code)).unwrap(),
+        "<div class=\"card ${cls}\" title=\"${title}\"></div>\n",
+    );
+}
+
+#[test]
+fn placeholder_as_text() {
+    let code = "<p>  Hello, ${name}!  </p>";
+    assert_eq!(
+        format_with_ranges(code, &find_interpolations(code)).unwrap(),
+        "<p>Hello, ${name}!</p>\n",
+    );
+}
+
+#[test]
+fn placeholder_token_is_substring_of_another() {
+    let code = (0..=10)
+        .map(|i| format!("<i>_{i}_</i>"))
+        .collect::<String>();
+    let expected = (0..=10)
+        .map(|i| format!("<i>_{i}_</i>"))
+        .collect::<Vec<_>>()
+        .join("");
+    let formatted = format_with_pattern(&format!("<p>{code}</p>"), "_{}_");
+    assert_eq!(
+        formatted.split_whitespace().collect::<String>(),
+        format!("<p>{expected}</p>"),
+    );
+}
+
+#[test]
+fn placeholder_keeps_original_width() {
+    let code = "<button ?disabled=${d} .value=${v} @click=${h}>x</button>";
+    assert_eq!(
+        format_with_ranges(code, &find_interpolations(code)).unwrap(),
+        "<button ?disabled=\"${d}\" .value=\"${v}\" @click=\"${h}\">x</button>\n",
+    );
+
+    // 80 columns with placeholders, but it doesn't fit with a longer token
+    let code = "<div>${a} <span>aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa</span></div>";
+    assert_eq!(code.len(), 80);
+    assert_eq!(
+        format_with_ranges(code, &find_interpolations(code)).unwrap(),
+        format!("{code}\n"),
+    );
+}
+
+#[test]
+fn invalid_placeholder_ranges() {
+    let code = "<div>${a}${b}</div>";
+    assert!(matches!(
+        format_with_ranges(code, &[5..10, 8..13]),
+        Err(FormatError::InvalidPlaceholder(range)) if range == (8..13),
+    ));
+    let out_of_bounds = 5..100;
+    assert!(matches!(
+        format_with_ranges(code, std::slice::from_ref(&out_of_bounds)),
+        Err(FormatError::InvalidPlaceholder(range)) if range == out_of_bounds,
+    ));
+}